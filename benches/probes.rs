@@ -3,7 +3,7 @@ use purr_lib::probe::{ProbeResultFunction, ProbeType};
 
 fn bench_probe(c: &mut Criterion, name: &str, probe_type: ProbeType) {
     let probe: ProbeResultFunction = probe_type.into();
-    c.bench_function(name, |b| b.iter(&probe));
+    c.bench_function(name, |b| b.iter(|| probe()));
 }
 
 // --- Fast probes (env vars, simple libmacchina reads) ---
@@ -15,6 +15,7 @@ fn fast_probes(c: &mut Criterion) {
     bench_probe(c, "probe/uptime", ProbeType::Uptime);
     bench_probe(c, "probe/shell", ProbeType::Shell);
     bench_probe(c, "probe/cpu", ProbeType::CPU);
+    bench_probe(c, "probe/cpu_temp", ProbeType::CpuTemp);
     bench_probe(c, "probe/memory", ProbeType::Memory);
     bench_probe(c, "probe/locale", ProbeType::Locale);
     bench_probe(c, "probe/editor", ProbeType::Editor);
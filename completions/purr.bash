@@ -16,6 +16,9 @@ _purr() {
             ",$1")
                 cmd="purr"
                 ;;
+            purr,config)
+                cmd="purr__subcmd__config"
+                ;;
             purr,config-path)
                 cmd="purr__subcmd__config__subcmd__path"
                 ;;
@@ -25,6 +28,33 @@ _purr() {
             purr,help)
                 cmd="purr__subcmd__help"
                 ;;
+            purr__subcmd__config,check)
+                cmd="purr__subcmd__config__subcmd__check"
+                ;;
+            purr__subcmd__config,help)
+                cmd="purr__subcmd__config__subcmd__help"
+                ;;
+            purr__subcmd__config,schema)
+                cmd="purr__subcmd__config__subcmd__schema"
+                ;;
+            purr__subcmd__config,show)
+                cmd="purr__subcmd__config__subcmd__show"
+                ;;
+            purr__subcmd__config__subcmd__help,check)
+                cmd="purr__subcmd__config__subcmd__help__subcmd__check"
+                ;;
+            purr__subcmd__config__subcmd__help,help)
+                cmd="purr__subcmd__config__subcmd__help__subcmd__help"
+                ;;
+            purr__subcmd__config__subcmd__help,schema)
+                cmd="purr__subcmd__config__subcmd__help__subcmd__schema"
+                ;;
+            purr__subcmd__config__subcmd__help,show)
+                cmd="purr__subcmd__config__subcmd__help__subcmd__show"
+                ;;
+            purr__subcmd__help,config)
+                cmd="purr__subcmd__help__subcmd__config"
+                ;;
             purr__subcmd__help,config-path)
                 cmd="purr__subcmd__help__subcmd__config__subcmd__path"
                 ;;
@@ -34,6 +64,15 @@ _purr() {
             purr__subcmd__help,help)
                 cmd="purr__subcmd__help__subcmd__help"
                 ;;
+            purr__subcmd__help__subcmd__config,check)
+                cmd="purr__subcmd__help__subcmd__config__subcmd__check"
+                ;;
+            purr__subcmd__help__subcmd__config,schema)
+                cmd="purr__subcmd__help__subcmd__config__subcmd__schema"
+                ;;
+            purr__subcmd__help__subcmd__config,show)
+                cmd="purr__subcmd__help__subcmd__config__subcmd__show"
+                ;;
             *)
                 ;;
         esac
@@ -41,7 +80,7 @@ _purr() {
 
     case "${cmd}" in
         purr)
-            opts="-c -n -L -h -V --verbose --config --no-config --all --neofetch --json --ascii_distro --ascii_colors --no_ascii_bold --logo --off --backend --source --separator --no_bold --underline_char --title_fqdn --colors --stdout --memory_unit --uptime_shorthand --cpu_cores --help --version generate config-path help"
+            opts="-c -n -L -h -V --verbose --config --no-config --all --neofetch --json --ascii_distro --ascii_colors --no_ascii_bold --logo --off --backend --source --separator --no_bold --underline_char --title_fqdn --colors --stdout --memory_unit --uptime_shorthand --cpu_cores --help --version generate config-path config help"
             if [[ ${cur} == -* || ${COMP_CWORD} -eq 1 ]] ; then
                 COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
                 return 0
@@ -102,8 +141,22 @@ _purr() {
             COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
             return 0
             ;;
+        purr__subcmd__config)
+            opts="-h --verbose --help check schema show help"
+            if [[ ${cur} == -* || ${COMP_CWORD} -eq 2 ]] ; then
+                COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+                return 0
+            fi
+            case "${prev}" in
+                *)
+                    COMPREPLY=()
+                    ;;
+            esac
+            COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+            return 0
+            ;;
         purr__subcmd__config__subcmd__path)
-            opts="-h --verbose --help"
+            opts="-h --all --verbose --help"
             if [[ ${cur} == -* || ${COMP_CWORD} -eq 2 ]] ; then
                 COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
                 return 0
@@ -116,13 +169,133 @@ _purr() {
             COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
             return 0
             ;;
+        purr__subcmd__config__subcmd__check)
+            opts="-h --verbose --help [PATH]"
+            if [[ ${cur} == -* || ${COMP_CWORD} -eq 3 ]] ; then
+                COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+                return 0
+            fi
+            case "${prev}" in
+                *)
+                    COMPREPLY=()
+                    ;;
+            esac
+            COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+            return 0
+            ;;
+        purr__subcmd__config__subcmd__help)
+            opts="check schema show help"
+            if [[ ${cur} == -* || ${COMP_CWORD} -eq 3 ]] ; then
+                COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+                return 0
+            fi
+            case "${prev}" in
+                *)
+                    COMPREPLY=()
+                    ;;
+            esac
+            COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+            return 0
+            ;;
+        purr__subcmd__config__subcmd__help__subcmd__check)
+            opts=""
+            if [[ ${cur} == -* || ${COMP_CWORD} -eq 4 ]] ; then
+                COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+                return 0
+            fi
+            case "${prev}" in
+                *)
+                    COMPREPLY=()
+                    ;;
+            esac
+            COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+            return 0
+            ;;
+        purr__subcmd__config__subcmd__help__subcmd__help)
+            opts=""
+            if [[ ${cur} == -* || ${COMP_CWORD} -eq 4 ]] ; then
+                COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+                return 0
+            fi
+            case "${prev}" in
+                *)
+                    COMPREPLY=()
+                    ;;
+            esac
+            COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+            return 0
+            ;;
+        purr__subcmd__config__subcmd__help__subcmd__schema)
+            opts=""
+            if [[ ${cur} == -* || ${COMP_CWORD} -eq 4 ]] ; then
+                COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+                return 0
+            fi
+            case "${prev}" in
+                *)
+                    COMPREPLY=()
+                    ;;
+            esac
+            COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+            return 0
+            ;;
+        purr__subcmd__config__subcmd__help__subcmd__show)
+            opts=""
+            if [[ ${cur} == -* || ${COMP_CWORD} -eq 4 ]] ; then
+                COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+                return 0
+            fi
+            case "${prev}" in
+                *)
+                    COMPREPLY=()
+                    ;;
+            esac
+            COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+            return 0
+            ;;
+        purr__subcmd__config__subcmd__schema)
+            opts="-h --verbose --help"
+            if [[ ${cur} == -* || ${COMP_CWORD} -eq 3 ]] ; then
+                COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+                return 0
+            fi
+            case "${prev}" in
+                *)
+                    COMPREPLY=()
+                    ;;
+            esac
+            COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+            return 0
+            ;;
+        purr__subcmd__config__subcmd__show)
+            opts="-h --format --origins --verbose --help"
+            if [[ ${cur} == -* || ${COMP_CWORD} -eq 3 ]] ; then
+                COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+                return 0
+            fi
+            case "${prev}" in
+                --format)
+                    COMPREPLY=($(compgen -W "toml json" -- "${cur}"))
+                    return 0
+                    ;;
+                *)
+                    COMPREPLY=()
+                    ;;
+            esac
+            COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+            return 0
+            ;;
         purr__subcmd__generate)
-            opts="-n -h --neofetch --all --verbose --help"
+            opts="-n -h --neofetch --all --from-neofetch --verbose --help"
             if [[ ${cur} == -* || ${COMP_CWORD} -eq 2 ]] ; then
                 COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
                 return 0
             fi
             case "${prev}" in
+                --from-neofetch)
+                    COMPREPLY=($(compgen -f "${cur}"))
+                    return 0
+                    ;;
                 *)
                     COMPREPLY=()
                     ;;
@@ -131,7 +304,7 @@ _purr() {
             return 0
             ;;
         purr__subcmd__help)
-            opts="generate config-path help"
+            opts="generate config-path config help"
             if [[ ${cur} == -* || ${COMP_CWORD} -eq 2 ]] ; then
                 COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
                 return 0
@@ -144,6 +317,20 @@ _purr() {
             COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
             return 0
             ;;
+        purr__subcmd__help__subcmd__config)
+            opts="check schema show"
+            if [[ ${cur} == -* || ${COMP_CWORD} -eq 3 ]] ; then
+                COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+                return 0
+            fi
+            case "${prev}" in
+                *)
+                    COMPREPLY=()
+                    ;;
+            esac
+            COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+            return 0
+            ;;
         purr__subcmd__help__subcmd__config__subcmd__path)
             opts=""
             if [[ ${cur} == -* || ${COMP_CWORD} -eq 3 ]] ; then
@@ -158,6 +345,48 @@ _purr() {
             COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
             return 0
             ;;
+        purr__subcmd__help__subcmd__config__subcmd__check)
+            opts=""
+            if [[ ${cur} == -* || ${COMP_CWORD} -eq 4 ]] ; then
+                COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+                return 0
+            fi
+            case "${prev}" in
+                *)
+                    COMPREPLY=()
+                    ;;
+            esac
+            COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+            return 0
+            ;;
+        purr__subcmd__help__subcmd__config__subcmd__schema)
+            opts=""
+            if [[ ${cur} == -* || ${COMP_CWORD} -eq 4 ]] ; then
+                COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+                return 0
+            fi
+            case "${prev}" in
+                *)
+                    COMPREPLY=()
+                    ;;
+            esac
+            COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+            return 0
+            ;;
+        purr__subcmd__help__subcmd__config__subcmd__show)
+            opts=""
+            if [[ ${cur} == -* || ${COMP_CWORD} -eq 4 ]] ; then
+                COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+                return 0
+            fi
+            case "${prev}" in
+                *)
+                    COMPREPLY=()
+                    ;;
+            esac
+            COMPREPLY=( $(compgen -W "${opts}" -- "${cur}") )
+            return 0
+            ;;
         purr__subcmd__help__subcmd__generate)
             opts=""
             if [[ ${cur} == -* || ${COMP_CWORD} -eq 3 ]] ; then
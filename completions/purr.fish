@@ -27,8 +27,8 @@ end
 complete -c purr -n "__fish_purr_needs_command" -s c -l config -d 'Path to a custom config file' -r -F
 complete -c purr -n "__fish_purr_needs_command" -l ascii_distro -d 'Force a specific distro logo (e.g. "arch")' -r
 complete -c purr -n "__fish_purr_needs_command" -l ascii_colors -d 'Override logo colours (space/comma list, e.g. "4 6 1")' -r
-complete -c purr -n "__fish_purr_needs_command" -l backend -d 'Logo backend: ascii or kitty' -r
-complete -c purr -n "__fish_purr_needs_command" -l source -d 'Image source (PNG) for the kitty backend' -r -F
+complete -c purr -n "__fish_purr_needs_command" -l backend -d 'Logo backend: ascii, kitty, iterm2, sixel, blocks or off' -r
+complete -c purr -n "__fish_purr_needs_command" -l source -d 'Image source (PNG, JPEG, GIF or WebP); selects the kitty backend unless another image backend is set' -r -F
 complete -c purr -n "__fish_purr_needs_command" -l separator -d 'Separator between labels and values' -r
 complete -c purr -n "__fish_purr_needs_command" -l underline_char -d 'Character used for the title underline' -r
 complete -c purr -n "__fish_purr_needs_command" -l colors -d 'Override text colours (space/comma list)' -r
@@ -50,13 +50,39 @@ complete -c purr -n "__fish_purr_needs_command" -s h -l help -d 'Print help'
 complete -c purr -n "__fish_purr_needs_command" -s V -l version -d 'Print version'
 complete -c purr -n "__fish_purr_needs_command" -f -a "generate" -d 'Generate a new config file'
 complete -c purr -n "__fish_purr_needs_command" -f -a "config-path" -d 'Return default config file path'
+complete -c purr -n "__fish_purr_needs_command" -f -a "config" -d 'Inspect config files'
 complete -c purr -n "__fish_purr_needs_command" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c purr -n "__fish_purr_using_subcommand generate" -l from-neofetch -d 'Translate a neofetch config (e.g. `~/.config/neofetch/config.conf`) instead of writing a preset' -r -F
 complete -c purr -n "__fish_purr_using_subcommand generate" -s n -l neofetch -d 'Generate neofetch preset'
 complete -c purr -n "__fish_purr_using_subcommand generate" -l all -d 'Use all default presets'
 complete -c purr -n "__fish_purr_using_subcommand generate" -l verbose -d 'Include verbose output or not'
 complete -c purr -n "__fish_purr_using_subcommand generate" -s h -l help -d 'Print help'
+complete -c purr -n "__fish_purr_using_subcommand config-path" -l all -d 'List every config file that is loaded, in merge order: system-wide, drop-ins, per-user, then `PURR_CONFIG`'
 complete -c purr -n "__fish_purr_using_subcommand config-path" -l verbose -d 'Include verbose output or not'
 complete -c purr -n "__fish_purr_using_subcommand config-path" -s h -l help -d 'Print help'
-complete -c purr -n "__fish_purr_using_subcommand help; and not __fish_seen_subcommand_from generate config-path help" -f -a "generate" -d 'Generate a new config file'
-complete -c purr -n "__fish_purr_using_subcommand help; and not __fish_seen_subcommand_from generate config-path help" -f -a "config-path" -d 'Return default config file path'
-complete -c purr -n "__fish_purr_using_subcommand help; and not __fish_seen_subcommand_from generate config-path help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c purr -n "__fish_purr_using_subcommand config; and not __fish_seen_subcommand_from check schema show help" -l verbose -d 'Include verbose output or not'
+complete -c purr -n "__fish_purr_using_subcommand config; and not __fish_seen_subcommand_from check schema show help" -s h -l help -d 'Print help'
+complete -c purr -n "__fish_purr_using_subcommand config; and not __fish_seen_subcommand_from check schema show help" -f -a "check" -d 'Check a config file for unknown keys, invalid values and duplicate probes, reporting each with its line and column'
+complete -c purr -n "__fish_purr_using_subcommand config; and not __fish_seen_subcommand_from check schema show help" -f -a "schema" -d 'Print a JSON Schema for the config file, for editor completion and validation (e.g. taplo)'
+complete -c purr -n "__fish_purr_using_subcommand config; and not __fish_seen_subcommand_from check schema show help" -f -a "show" -d 'Print the effective config: defaults, config files and command-line flags merged, as `purr` would render with them'
+complete -c purr -n "__fish_purr_using_subcommand config; and not __fish_seen_subcommand_from check schema show help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c purr -n "__fish_purr_using_subcommand config; and __fish_seen_subcommand_from check" -l verbose -d 'Include verbose output or not'
+complete -c purr -n "__fish_purr_using_subcommand config; and __fish_seen_subcommand_from check" -s h -l help -d 'Print help'
+complete -c purr -n "__fish_purr_using_subcommand config; and __fish_seen_subcommand_from schema" -l verbose -d 'Include verbose output or not'
+complete -c purr -n "__fish_purr_using_subcommand config; and __fish_seen_subcommand_from schema" -s h -l help -d 'Print help'
+complete -c purr -n "__fish_purr_using_subcommand config; and __fish_seen_subcommand_from show" -l format -d 'Output format' -r -f -a "toml\t''
+json\t''"
+complete -c purr -n "__fish_purr_using_subcommand config; and __fish_seen_subcommand_from show" -l origins -d 'Note which layer (default, a config file, or the command line) set each value'
+complete -c purr -n "__fish_purr_using_subcommand config; and __fish_seen_subcommand_from show" -l verbose -d 'Include verbose output or not'
+complete -c purr -n "__fish_purr_using_subcommand config; and __fish_seen_subcommand_from show" -s h -l help -d 'Print help'
+complete -c purr -n "__fish_purr_using_subcommand config; and __fish_seen_subcommand_from help" -f -a "check" -d 'Check a config file for unknown keys, invalid values and duplicate probes, reporting each with its line and column'
+complete -c purr -n "__fish_purr_using_subcommand config; and __fish_seen_subcommand_from help" -f -a "schema" -d 'Print a JSON Schema for the config file, for editor completion and validation (e.g. taplo)'
+complete -c purr -n "__fish_purr_using_subcommand config; and __fish_seen_subcommand_from help" -f -a "show" -d 'Print the effective config: defaults, config files and command-line flags merged, as `purr` would render with them'
+complete -c purr -n "__fish_purr_using_subcommand config; and __fish_seen_subcommand_from help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c purr -n "__fish_purr_using_subcommand help; and not __fish_seen_subcommand_from generate config-path config help" -f -a "generate" -d 'Generate a new config file'
+complete -c purr -n "__fish_purr_using_subcommand help; and not __fish_seen_subcommand_from generate config-path config help" -f -a "config-path" -d 'Return default config file path'
+complete -c purr -n "__fish_purr_using_subcommand help; and not __fish_seen_subcommand_from generate config-path config help" -f -a "config" -d 'Inspect config files'
+complete -c purr -n "__fish_purr_using_subcommand help; and not __fish_seen_subcommand_from generate config-path config help" -f -a "help" -d 'Print this message or the help of the given subcommand(s)'
+complete -c purr -n "__fish_purr_using_subcommand help; and __fish_seen_subcommand_from config" -f -a "check" -d 'Check a config file for unknown keys, invalid values and duplicate probes, reporting each with its line and column'
+complete -c purr -n "__fish_purr_using_subcommand help; and __fish_seen_subcommand_from config" -f -a "schema" -d 'Print a JSON Schema for the config file, for editor completion and validation (e.g. taplo)'
+complete -c purr -n "__fish_purr_using_subcommand help; and __fish_seen_subcommand_from config" -f -a "show" -d 'Print the effective config: defaults, config files and command-line flags merged, as `purr` would render with them'
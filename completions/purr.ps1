@@ -25,8 +25,8 @@ Register-ArgumentCompleter -Native -CommandName 'purr' -ScriptBlock {
             [CompletionResult]::new('--config', '--config', [CompletionResultType]::ParameterName, 'Path to a custom config file')
             [CompletionResult]::new('--ascii_distro', '--ascii_distro', [CompletionResultType]::ParameterName, 'Force a specific distro logo (e.g. "arch")')
             [CompletionResult]::new('--ascii_colors', '--ascii_colors', [CompletionResultType]::ParameterName, 'Override logo colours (space/comma list, e.g. "4 6 1")')
-            [CompletionResult]::new('--backend', '--backend', [CompletionResultType]::ParameterName, 'Logo backend: ascii or kitty')
-            [CompletionResult]::new('--source', '--source', [CompletionResultType]::ParameterName, 'Image source (PNG) for the kitty backend')
+            [CompletionResult]::new('--backend', '--backend', [CompletionResultType]::ParameterName, 'Logo backend: ascii, kitty, iterm2, sixel, blocks or off')
+            [CompletionResult]::new('--source', '--source', [CompletionResultType]::ParameterName, 'Image source (PNG, JPEG, GIF or WebP); selects the kitty backend unless another image backend is set')
             [CompletionResult]::new('--separator', '--separator', [CompletionResultType]::ParameterName, 'Separator between labels and values')
             [CompletionResult]::new('--underline_char', '--underline_char', [CompletionResultType]::ParameterName, 'Character used for the title underline')
             [CompletionResult]::new('--colors', '--colors', [CompletionResultType]::ParameterName, 'Override text colours (space/comma list)')
@@ -52,10 +52,12 @@ Register-ArgumentCompleter -Native -CommandName 'purr' -ScriptBlock {
             [CompletionResult]::new('--version', '--version', [CompletionResultType]::ParameterName, 'Print version')
             [CompletionResult]::new('generate', 'generate', [CompletionResultType]::ParameterValue, 'Generate a new config file')
             [CompletionResult]::new('config-path', 'config-path', [CompletionResultType]::ParameterValue, 'Return default config file path')
+            [CompletionResult]::new('config', 'config', [CompletionResultType]::ParameterValue, 'Inspect config files')
             [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
             break
         }
         'purr;generate' {
+            [CompletionResult]::new('--from-neofetch', '--from-neofetch', [CompletionResultType]::ParameterName, 'Translate a neofetch config (e.g. `~/.config/neofetch/config.conf`) instead of writing a preset')
             [CompletionResult]::new('-n', '-n', [CompletionResultType]::ParameterName, 'Generate neofetch preset')
             [CompletionResult]::new('--neofetch', '--neofetch', [CompletionResultType]::ParameterName, 'Generate neofetch preset')
             [CompletionResult]::new('--all', '--all', [CompletionResultType]::ParameterName, 'Use all default presets')
@@ -65,14 +67,65 @@ Register-ArgumentCompleter -Native -CommandName 'purr' -ScriptBlock {
             break
         }
         'purr;config-path' {
+            [CompletionResult]::new('--all', '--all', [CompletionResultType]::ParameterName, 'List every config file that is loaded, in merge order: system-wide, drop-ins, per-user, then `PURR_CONFIG`')
             [CompletionResult]::new('--verbose', '--verbose', [CompletionResultType]::ParameterName, 'Include verbose output or not')
             [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
             [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
             break
         }
+        'purr;config' {
+            [CompletionResult]::new('--verbose', '--verbose', [CompletionResultType]::ParameterName, 'Include verbose output or not')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('check', 'check', [CompletionResultType]::ParameterValue, 'Check a config file for unknown keys, invalid values and duplicate probes, reporting each with its line and column')
+            [CompletionResult]::new('schema', 'schema', [CompletionResultType]::ParameterValue, 'Print a JSON Schema for the config file, for editor completion and validation (e.g. taplo)')
+            [CompletionResult]::new('show', 'show', [CompletionResultType]::ParameterValue, 'Print the effective config: defaults, config files and command-line flags merged, as `purr` would render with them')
+            [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
+            break
+        }
+        'purr;config;check' {
+            [CompletionResult]::new('--verbose', '--verbose', [CompletionResultType]::ParameterName, 'Include verbose output or not')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'purr;config;schema' {
+            [CompletionResult]::new('--verbose', '--verbose', [CompletionResultType]::ParameterName, 'Include verbose output or not')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'purr;config;show' {
+            [CompletionResult]::new('--format', '--format', [CompletionResultType]::ParameterName, 'Output format')
+            [CompletionResult]::new('--origins', '--origins', [CompletionResultType]::ParameterName, 'Note which layer (default, a config file, or the command line) set each value')
+            [CompletionResult]::new('--verbose', '--verbose', [CompletionResultType]::ParameterName, 'Include verbose output or not')
+            [CompletionResult]::new('-h', '-h', [CompletionResultType]::ParameterName, 'Print help')
+            [CompletionResult]::new('--help', '--help', [CompletionResultType]::ParameterName, 'Print help')
+            break
+        }
+        'purr;config;help' {
+            [CompletionResult]::new('check', 'check', [CompletionResultType]::ParameterValue, 'Check a config file for unknown keys, invalid values and duplicate probes, reporting each with its line and column')
+            [CompletionResult]::new('schema', 'schema', [CompletionResultType]::ParameterValue, 'Print a JSON Schema for the config file, for editor completion and validation (e.g. taplo)')
+            [CompletionResult]::new('show', 'show', [CompletionResultType]::ParameterValue, 'Print the effective config: defaults, config files and command-line flags merged, as `purr` would render with them')
+            [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
+            break
+        }
+        'purr;config;help;check' {
+            break
+        }
+        'purr;config;help;schema' {
+            break
+        }
+        'purr;config;help;show' {
+            break
+        }
+        'purr;config;help;help' {
+            break
+        }
         'purr;help' {
             [CompletionResult]::new('generate', 'generate', [CompletionResultType]::ParameterValue, 'Generate a new config file')
             [CompletionResult]::new('config-path', 'config-path', [CompletionResultType]::ParameterValue, 'Return default config file path')
+            [CompletionResult]::new('config', 'config', [CompletionResultType]::ParameterValue, 'Inspect config files')
             [CompletionResult]::new('help', 'help', [CompletionResultType]::ParameterValue, 'Print this message or the help of the given subcommand(s)')
             break
         }
@@ -82,6 +135,21 @@ Register-ArgumentCompleter -Native -CommandName 'purr' -ScriptBlock {
         'purr;help;config-path' {
             break
         }
+        'purr;help;config' {
+            [CompletionResult]::new('check', 'check', [CompletionResultType]::ParameterValue, 'Check a config file for unknown keys, invalid values and duplicate probes, reporting each with its line and column')
+            [CompletionResult]::new('schema', 'schema', [CompletionResultType]::ParameterValue, 'Print a JSON Schema for the config file, for editor completion and validation (e.g. taplo)')
+            [CompletionResult]::new('show', 'show', [CompletionResultType]::ParameterValue, 'Print the effective config: defaults, config files and command-line flags merged, as `purr` would render with them')
+            break
+        }
+        'purr;help;config;check' {
+            break
+        }
+        'purr;help;config;schema' {
+            break
+        }
+        'purr;help;config;show' {
+            break
+        }
         'purr;help;help' {
             break
         }
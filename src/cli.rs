@@ -50,10 +50,10 @@ pub struct Cli {
     /// Hide the logo.
     #[clap(long)]
     pub off: bool,
-    /// Logo backend: ascii or kitty.
+    /// Logo backend: ascii, kitty, iterm2, sixel, blocks or off.
     #[clap(long, value_name = "BACKEND")]
     pub backend: Option<String>,
-    /// Image source (PNG) for the kitty backend.
+    /// Image source (PNG, JPEG, GIF or WebP); selects the kitty backend unless another image backend is set.
     #[clap(long, value_name = "PATH")]
     pub source: Option<PathBuf>,
 
@@ -99,7 +99,40 @@ pub enum Command {
     /// Generate a new config file
     Generate(GenerateCommandArgs),
     /// Return default config file path
-    ConfigPath,
+    ConfigPath {
+        /// List every config file that is loaded, in merge order: system-wide,
+        /// drop-ins, per-user, then `PURR_CONFIG`.
+        #[clap(long)]
+        all: bool,
+    },
+    /// Inspect config files
+    #[clap(subcommand)]
+    Config(ConfigCommand),
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommand {
+    /// Check a config file for unknown keys, invalid values and duplicate
+    /// probes, reporting each with its line and column
+    Check {
+        /// Config file to check (defaults to `--config`, then every file on the
+        /// search path).
+        path: Option<PathBuf>,
+    },
+    /// Print a JSON Schema for the config file, for editor completion and
+    /// validation (e.g. taplo)
+    Schema,
+    /// Print the effective config: defaults, config files and command-line
+    /// flags merged, as `purr` would render with them
+    Show {
+        /// Output format.
+        #[clap(long, value_name = "FORMAT", value_parser = ["toml", "json"], default_value = "toml")]
+        format: String,
+        /// Note which layer (default, a config file, or the command line) set
+        /// each value.
+        #[clap(long)]
+        origins: bool,
+    },
 }
 
 #[derive(Parser, Debug)]
@@ -111,4 +144,9 @@ pub struct GenerateCommandArgs {
     /// Use all default presets.
     #[clap(long)]
     pub all: bool,
+
+    /// Translate a neofetch config (e.g. `~/.config/neofetch/config.conf`)
+    /// instead of writing a preset.
+    #[clap(long, value_name = "PATH", conflicts_with_all = ["neofetch", "all"])]
+    pub from_neofetch: Option<PathBuf>,
 }
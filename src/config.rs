@@ -1,21 +1,99 @@
-use std::path::{Path, PathBuf};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
 
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::probe::{ProbeResultFunction, ProbeType, ProbeValue};
+use crate::probe::{
+    BiosInfo, BluetoothItem, NetInterface, ProbeResultFunction, ProbeResultValue, ProbeType,
+    ProbeValue,
+};
+
+pub mod annotate;
+pub mod check;
+pub mod condition;
+pub mod import;
+pub mod meta;
+pub mod schema;
+pub mod show;
+
+use meta::{Describe, Field, Kind};
+
+/// Concatenated `#[doc]` text out of a field's attribute list.
+macro_rules! field_doc {
+    () => { "" };
+    (#[doc = $doc:literal] $($rest:tt)*) => { concat!($doc, "\n", field_doc!($($rest)*)) };
+    (#[$($attr:tt)*] $($rest:tt)*) => { field_doc!($($rest)*) };
+}
+
+/// Whether an attribute list carries a `#[serde(default)]` (or
+/// `#[serde(default = "...")]`), i.e. serde fills the value in when it's missing.
+macro_rules! serde_default {
+    () => { false };
+    (@args [$($rest:tt)*]) => { serde_default!($($rest)*) };
+    (@args [$($rest:tt)*] default $($args:tt)*) => { true };
+    (@args [$($rest:tt)*] $skip:tt $($args:tt)*) => { serde_default!(@args [$($rest)*] $($args)*) };
+    (#[serde($($args:tt)*)] $($rest:tt)*) => { serde_default!(@args [$($rest)*] $($args)*) };
+    (#[$($attr:tt)*] $($rest:tt)*) => { serde_default!($($rest)*) };
+}
 
+/// Define a config struct and its [`Describe`] impl from the same field list,
+/// so the description (names, types, docs) follows the struct. A field is
+/// required unless serde can fill it in: a container or field `default`, or
+/// an `Option`.
+macro_rules! described {
+    (
+        $(#[$($m:tt)*])*
+        $vis:vis struct $name:ident {
+            $($(#[$($fattr:tt)*])* $fvis:vis $field:ident : $ty:ty),* $(,)?
+        }
+    ) => {
+        $(#[$($m)*])*
+        $vis struct $name {
+            $($(#[$($fattr)*])* $fvis $field: $ty,)*
+        }
+
+        impl Describe for $name {
+            fn kind() -> Kind {
+                let container_default = serde_default!($(#[$($m)*])*);
+                Kind::Table(vec![$({
+                    let field = Field::new(
+                        stringify!($field),
+                        field_doc!($(#[$($fattr)*])*),
+                        <$ty as Describe>::kind(),
+                    );
+                    let optional = container_default
+                        || serde_default!($(#[$($fattr)*])*)
+                        || matches!(field.kind, Kind::Optional(_));
+                    if optional { field } else { field.required() }
+                },)*])
+            }
+        }
+    };
+}
+
+// Built once per run, so the Neofetch variant's size doesn't matter.
+#[allow(clippy::large_enum_variant)]
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub enum Config {
     Neofetch(NeofetchRendererConfig),
     Json(JsonRendererConfig),
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum RendererOverride {
     Neofetch,
     Json,
 }
 
+impl RendererOverride {
+    /// Top-level table names of the renderer sections in a config file.
+    const SECTIONS: [&'static str; 2] = ["Neofetch", "Json"];
+}
+
 impl Config {
     /// Default config with all features enabled
     pub fn default_all() -> Self {
@@ -37,20 +115,296 @@ impl Config {
         Self::Json(JsonRendererConfig::default())
     }
 
-    /// Load config from a file
+    /// Load config from a file. The file may hold a `[Neofetch]` section, a
+    /// `[Json]` section, or both (plus a top-level `probes` list they share);
+    /// `renderer_override` picks which one is used, defaulting to the
+    /// top-level `renderer` key, then whichever section is present. The file
+    /// (with what it `extends`) has to be a complete config.
     pub fn from_file(
         path: &Path,
-        _renderer_override: Option<RendererOverride>,
+        renderer_override: Option<RendererOverride>,
     ) -> Result<Self, ConfigParseError> {
-        // TODO: Support "extending" default configs
-        // TODO: Implement renderer override
-        toml::from_str(&std::fs::read_to_string(path)?).map_err(|e| e.into())
+        let doc = Self::load_table(path, &mut Vec::new(), None)?;
+        Self::from_table(doc, renderer_override, false)
+    }
+
+    /// Load config layers (see [`Config::config_files`]) in order, each one
+    /// building on the layers before it, as if it `extends` them. A layer's
+    /// own `extends` slots in beneath those layers. Layers only need the keys
+    /// they change: the rest come from the renderer's defaults, which is also
+    /// what no layers at all give.
+    pub fn from_files(
+        paths: &[PathBuf],
+        renderer_override: Option<RendererOverride>,
+    ) -> Result<Self, ConfigParseError> {
+        let mut doc = None;
+        for path in paths {
+            doc = Some(Self::load_table(path, &mut Vec::new(), doc)?);
+        }
+        match doc {
+            Some(doc) => Self::from_table(doc, renderer_override, true),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Load `path` as a merged document, resolving its `extends` chain (or
+    /// building on `below`, the layers loaded before it). `chain` holds the
+    /// files already being loaded, to reject cycles.
+    fn load_table(
+        path: &Path,
+        chain: &mut Vec<PathBuf>,
+        below: Option<toml::Table>,
+    ) -> Result<toml::Table, ConfigParseError> {
+        let canonical = path.canonicalize()?;
+        if chain.contains(&canonical) {
+            return Err(ConfigParseError::ExtendsCycle(canonical));
+        }
+        chain.push(canonical);
+        let dir = path.parent().unwrap_or(Path::new("."));
+        let table = Self::parse_table(&std::fs::read_to_string(path)?, dir, chain, below);
+        chain.pop();
+        table
+    }
+
+    /// Parse a config document, resolving a top-level `extends` against the
+    /// built-in presets (`neofetch`, `all`, `json`) or another file (relative
+    /// to `dir`) with `below` merged over it, else taking `below` as the base,
+    /// then deep-merging this
+    /// document on top: tables merge key by key, anything else (including a
+    /// full `probes` list) replaces. Each
+    /// renderer section may also edit the inherited probe list through
+    /// `probes_remove` (ids), `probes_replace` and `probes_append`. The
+    /// document's `when` sections and probes' `enabled_if` are resolved first,
+    /// against the running system (see [`condition`]).
+    fn parse_table(
+        src: &str,
+        dir: &Path,
+        chain: &mut Vec<PathBuf>,
+        below: Option<toml::Table>,
+    ) -> Result<toml::Table, ConfigParseError> {
+        let mut overlay: toml::Table = toml::from_str(src)?;
+        condition::resolve(&mut overlay, condition::Facts::current)?;
+        let base = match overlay.remove("extends") {
+            None => below,
+            Some(toml::Value::String(name)) => {
+                let mut base = match name.as_str() {
+                    "neofetch" => Self::default_neofetch().to_table()?,
+                    "all" => Self::default_all().to_table()?,
+                    "json" => Self::default_json().to_table()?,
+                    file => Self::load_table(&dir.join(file), chain, None)?,
+                };
+                // The layers already loaded still apply on top of it.
+                if let Some(below) = below {
+                    merge_tables(&mut base, below);
+                }
+                Some(base)
+            }
+            Some(_) => {
+                return Err(ConfigParseError::Invalid(
+                    "`extends` must be a preset name or a path".to_string(),
+                ));
+            }
+        };
+
+        let mut ops = Vec::new();
+        for key in RendererOverride::SECTIONS {
+            if let Some(toml::Value::Table(section)) = overlay.get_mut(key)
+                && let Some(op) = ProbeListOps::take(section)?
+            {
+                ops.push((key, op));
+            }
+        }
+
+        let mut merged = match base {
+            Some(mut base) => {
+                merge_tables(&mut base, overlay);
+                base
+            }
+            None => overlay,
+        };
+        for (key, op) in ops {
+            let mut probes = section_probes(&merged, key)?;
+            op.apply(&mut probes)?;
+            if let Some(toml::Value::Table(section)) = merged.get_mut(key) {
+                section.insert("probes".to_string(), toml::Value::try_from(&probes)?);
+            }
+        }
+        Ok(merged)
+    }
+
+    /// Pick the active renderer's section out of a merged document. A section
+    /// without its own `probes` uses the shared top-level list, else the other
+    /// section's; with no section for the target renderer, it gets the
+    /// renderer's defaults with that same list and the other section's
+    /// `probe_timeout`. With `partial`, the section only needs the keys it
+    /// sets and is merged over its renderer's defaults.
+    fn from_table(
+        mut table: toml::Table,
+        renderer_override: Option<RendererOverride>,
+        partial: bool,
+    ) -> Result<Self, ConfigParseError> {
+        let preferred = match table.remove("renderer") {
+            None => None,
+            Some(toml::Value::String(name)) => Some(match name.to_ascii_lowercase().as_str() {
+                "neofetch" => RendererOverride::Neofetch,
+                "json" => RendererOverride::Json,
+                _ => {
+                    return Err(ConfigParseError::Invalid(format!(
+                        "unknown renderer `{name}` (expected `neofetch` or `json`)"
+                    )));
+                }
+            }),
+            Some(_) => {
+                return Err(ConfigParseError::Invalid(
+                    "`renderer` must be `neofetch` or `json`".to_string(),
+                ));
+            }
+        };
+        let target = renderer_override.or(preferred).unwrap_or(
+            if !table.contains_key("Neofetch") && table.contains_key("Json") {
+                RendererOverride::Json
+            } else {
+                RendererOverride::Neofetch
+            },
+        );
+
+        for key in RendererOverride::SECTIONS {
+            if matches!(table.get(key), Some(toml::Value::Table(t)) if !t.contains_key("probes")) {
+                let probes = toml::Value::try_from(section_probes(&table, key)?)?;
+                if let Some(toml::Value::Table(section)) = table.get_mut(key) {
+                    section.insert("probes".to_string(), probes);
+                }
+            }
+        }
+
+        let (key, other) = match target {
+            RendererOverride::Neofetch => ("Neofetch", "Json"),
+            RendererOverride::Json => ("Json", "Neofetch"),
+        };
+        let (section, fill) = match table.remove(key) {
+            Some(section) => (section, partial),
+            // No section for the target renderer: its defaults, with the
+            // probe list and timeout the file does give.
+            None => {
+                let mut section = toml::Table::new();
+                section.insert(
+                    "probes".to_string(),
+                    toml::Value::try_from(section_probes(&table, key)?)?,
+                );
+                if let Some(timeout) = table.get(other).and_then(|s| s.get("probe_timeout")) {
+                    section.insert("probe_timeout".to_string(), timeout.clone());
+                }
+                (toml::Value::Table(section), true)
+            }
+        };
+        let section = match section {
+            toml::Value::Table(section) if fill => {
+                let defaults = match target {
+                    RendererOverride::Json => Self::default_json(),
+                    RendererOverride::Neofetch => Self::default_neofetch(),
+                };
+                let mut defaults = defaults.to_table()?;
+                let mut base = match defaults.remove(key) {
+                    Some(toml::Value::Table(base)) => base,
+                    _ => unreachable!("a config serializes its renderer section"),
+                };
+                merge_tables(&mut base, section);
+                toml::Value::Table(base)
+            }
+            section => section,
+        };
+        let mut single = toml::Table::new();
+        single.insert(key.to_string(), section);
+        Ok(toml::Value::Table(single).try_into()?)
+    }
+
+    /// The shape of a complete config file: the renderer sections (with their
+    /// probe list edits) plus the keys the loader resolves itself.
+    pub fn document_kind() -> Kind {
+        Self::describe_document(false)
+    }
+
+    /// The shape of a config file building on something else (an `extends`
+    /// target or lower layers): as [`Config::document_kind`], with no key
+    /// of a renderer section required.
+    pub fn layer_kind() -> Kind {
+        Self::describe_document(true)
+    }
+
+    fn describe_document(partial: bool) -> Kind {
+        // Renderer sections with none of their keys required.
+        let relaxed = |fields: &[Field]| -> Vec<Field> {
+            let mut fields = fields.to_vec();
+            for field in &mut fields {
+                if let Kind::Optional(inner) = &mut field.kind
+                    && let Kind::Table(section) = inner.as_mut()
+                {
+                    section.iter_mut().for_each(|f| f.required = false);
+                }
+            }
+            fields
+        };
+        let section = |kind: Kind| match (kind, ProbeListOps::kind()) {
+            (Kind::Table(mut fields), Kind::Table(ops)) => {
+                fields.extend(ops);
+                Kind::Optional(Box::new(Kind::Table(fields)))
+            }
+            _ => unreachable!("renderer sections describe as tables"),
+        };
+        let mut fields = vec![
+            Field::new(
+                "extends",
+                "Preset (`neofetch`, `all`, `json`) or file (relative to this one)\n\
+                 to build on.",
+                Kind::Optional(Box::new(Kind::Str)),
+            ),
+            Field::new(
+                "renderer",
+                "Section to use when the file has both.",
+                Kind::Optional(Box::new(Kind::Enum(&["neofetch", "json"]))),
+            ),
+            Field::new(
+                "probes",
+                "Probe list shared by sections without their own.",
+                Kind::Optional(Box::new(Kind::List(Box::new(Kind::Probe)))),
+            ),
+            Field::new(
+                "Neofetch",
+                "Text renderer settings.",
+                section(NeofetchRendererConfig::kind()),
+            ),
+            Field::new(
+                "Json",
+                "JSON renderer settings.",
+                section(JsonRendererConfig::kind()),
+            ),
+        ];
+        if partial {
+            fields = relaxed(&fields);
+        }
+        // `when` sections only ever add to the document they're in.
+        let when = condition::when_kind(&relaxed(&fields));
+        fields.push(Field::new(
+            "when",
+            "Sections merged on top when the host, OS, distro or environment\n\
+             matches, e.g. `[when.host.\"build-*\".Neofetch]`.",
+            Kind::Optional(Box::new(when)),
+        ));
+        Kind::Table(fields)
     }
 
-    /// Write config to a file
+    /// This config as a TOML document table.
+    fn to_table(&self) -> Result<toml::Table, ConfigParseError> {
+        match toml::Value::try_from(self)? {
+            toml::Value::Table(table) => Ok(table),
+            _ => unreachable!("a config serializes to a table"),
+        }
+    }
+
+    /// Write config to a file, with every option's doc comment
     pub fn to_file(&self, path: &Path) -> Result<(), ConfigWriteError> {
-        // Serialize to toml
-        let toml = toml::to_string(self)?;
+        // Serialize to commented toml
+        let toml = annotate::to_annotated_string(self)?;
         // Write to file
         Ok(std::fs::write(path, toml)?)
     }
@@ -66,7 +420,6 @@ impl Config {
         }
         let config = Self::default();
         config.to_file(path)
-        // TODO: Replace line above with custom serialization to include comments
     }
 
     fn get_project_dirs() -> Option<directories::ProjectDirs> {
@@ -77,6 +430,60 @@ impl Config {
         Self::get_project_dirs().map(|dirs| dirs.config_dir().to_path_buf())
     }
 
+    /// Directory of the system-wide config shared by every user.
+    pub fn get_system_config_dir() -> Option<PathBuf> {
+        if cfg!(windows) {
+            std::env::var_os("ProgramData").map(|dir| PathBuf::from(dir).join("purr"))
+        } else {
+            Some(PathBuf::from("/etc/purr"))
+        }
+    }
+
+    /// The config files to load, in order: the system-wide `config.toml`, its
+    /// `config.d/*.toml` drop-ins (by name), the user's `config.toml`, then
+    /// the file named by `PURR_CONFIG`. Only files that exist are listed,
+    /// except `PURR_CONFIG`, which is an explicit request.
+    pub fn config_files() -> Vec<PathBuf> {
+        Self::layer_files(
+            Self::get_system_config_dir().as_deref(),
+            Self::get_config_dir().as_deref(),
+            std::env::var_os(Self::CONFIG_ENV)
+                .filter(|p| !p.is_empty())
+                .map(PathBuf::from),
+        )
+    }
+
+    fn layer_files(
+        system: Option<&Path>,
+        user: Option<&Path>,
+        env: Option<PathBuf>,
+    ) -> Vec<PathBuf> {
+        let mut files = Vec::new();
+        if let Some(system) = system {
+            files.push(system.join(Self::CONFIG_FILE_NAME));
+            let mut drop_ins: Vec<PathBuf> = std::fs::read_dir(system.join("config.d"))
+                .into_iter()
+                .flatten()
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|p| p.extension().is_some_and(|ext| ext == "toml"))
+                .collect();
+            drop_ins.sort();
+            files.extend(drop_ins);
+        }
+        files.extend(user.map(|dir| dir.join(Self::CONFIG_FILE_NAME)));
+        files.retain(|p| p.is_file());
+        files.extend(env);
+        files
+    }
+
+    /// The renderer this config is for.
+    pub fn renderer(&self) -> RendererOverride {
+        match self {
+            Config::Neofetch(_) => RendererOverride::Neofetch,
+            Config::Json(_) => RendererOverride::Json,
+        }
+    }
+
     /// Mutable access to the active renderer's probe list (for CLI overrides).
     pub fn probes_mut(&mut self) -> &mut Vec<ProbeConfig> {
         match self {
@@ -86,26 +493,32 @@ impl Config {
     }
 
     /// Swap to a different renderer, preserving the probe list. A Neofetch
-    /// config kept as Neofetch is returned untouched (no styling loss).
+    /// config kept as Neofetch is returned untouched (no styling loss); a Json
+    /// one gets the Neofetch defaults, so a config file is better loaded with
+    /// the renderer override, which keeps the file's own `[Neofetch]` section.
     pub fn with_renderer(self, target: RendererOverride) -> Self {
         match (target, &self) {
             (RendererOverride::Neofetch, Config::Neofetch(_)) => self,
             (RendererOverride::Neofetch, Config::Json(c)) => {
                 Config::Neofetch(NeofetchRendererConfig {
+                    probe_timeout: c.probe_timeout,
                     probes: c.probes.clone(),
                     ..Default::default()
                 })
             }
-            (RendererOverride::Json, _) => Config::Json(JsonRendererConfig {
-                probes: match self {
-                    Config::Neofetch(c) => c.probes,
-                    Config::Json(c) => c.probes,
+            (RendererOverride::Json, _) => Config::Json(match self {
+                Config::Neofetch(c) => JsonRendererConfig {
+                    probe_timeout: c.probe_timeout,
+                    probes: c.probes,
                 },
+                Config::Json(c) => c,
             }),
         }
     }
 
     pub const CONFIG_FILE_NAME: &'static str = "config.toml";
+    /// Environment variable naming a config file layered over all others.
+    pub const CONFIG_ENV: &'static str = "PURR_CONFIG";
 }
 
 impl Default for Config {
@@ -120,6 +533,104 @@ pub enum ConfigParseError {
     Io(#[from] std::io::Error),
     #[error("Deserialization error: {0}")]
     Deserialization(#[from] toml::de::Error),
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] toml::ser::Error),
+    #[error("Config extends itself: {}", .0.display())]
+    ExtendsCycle(PathBuf),
+    #[error("Invalid config: {0}")]
+    Invalid(String),
+}
+
+/// Recursively merge `overlay` into `base`: nested tables merge key by key,
+/// every other value in `overlay` replaces the one in `base`.
+fn merge_tables(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(b)), toml::Value::Table(o)) => merge_tables(b, o),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// The probe list a renderer section in `doc` works with: its own `probes`,
+/// else the shared top-level `probes`, else the other section's, else the
+/// renderer's defaults.
+fn section_probes(doc: &toml::Table, key: &str) -> Result<Vec<ProbeConfig>, ConfigParseError> {
+    let own = doc.get(key).and_then(|s| s.get("probes"));
+    let other = RendererOverride::SECTIONS
+        .iter()
+        .filter(|k| **k != key)
+        .find_map(|k| doc.get(*k).and_then(|s| s.get("probes")));
+    match own.or(doc.get("probes")).or(other) {
+        Some(probes) => Ok(probes.clone().try_into()?),
+        None if key == "Json" => Ok(ProbeConfig::default_all()),
+        None => Ok(ProbeConfig::default_neofetch()),
+    }
+}
+
+described! {
+    /// Edits an `extends` overlay makes to the inherited probe list, applied in
+    /// order: remove by probe id, replace the probe with the same id (or append
+    /// when there is none), then append. An id must name at most one inherited
+    /// probe; repeatable entries (e.g. two `Disk` or `Text` lines) are edited by
+    /// restating `probes` instead.
+    #[derive(Default, Deserialize)]
+    #[serde(default)]
+    struct ProbeListOps {
+        /// Ids of inherited probes to drop (e.g. `"memory"`).
+        probes_remove: Vec<String>,
+        /// Probes replacing the inherited probe with the same id.
+        probes_replace: Vec<ProbeConfig>,
+        /// Probes added after the inherited list.
+        probes_append: Vec<ProbeConfig>,
+    }
+}
+
+impl ProbeListOps {
+    const KEYS: [&'static str; 3] = ["probes_remove", "probes_replace", "probes_append"];
+
+    /// Remove the op keys from a renderer section and parse them; `None` when
+    /// the section has none, so its inherited list stays as it is.
+    fn take(section: &mut toml::Table) -> Result<Option<Self>, ConfigParseError> {
+        let ops: toml::Table = Self::KEYS
+            .iter()
+            .filter_map(|k| section.remove(*k).map(|v| (k.to_string(), v)))
+            .collect();
+        if ops.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(toml::Value::Table(ops).try_into()?))
+    }
+
+    fn apply(self, probes: &mut Vec<ProbeConfig>) -> Result<(), ConfigParseError> {
+        let removed = self
+            .probes_remove
+            .iter()
+            .map(|id| ("probes_remove", id.as_str()));
+        let replaced = self
+            .probes_replace
+            .iter()
+            .map(|p| ("probes_replace", p.id()));
+        for (key, id) in removed.chain(replaced) {
+            let matches = probes.iter().filter(|p| p.id() == id).count();
+            if matches > 1 {
+                return Err(ConfigParseError::Invalid(format!(
+                    "`{key}` id `{id}` matches {matches} inherited probes; restate `probes` instead"
+                )));
+            }
+        }
+        probes.retain(|p| !self.probes_remove.iter().any(|id| id == p.id()));
+        for probe in self.probes_replace {
+            match probes.iter_mut().find(|p| p.id() == probe.id()) {
+                Some(slot) => *slot = probe,
+                None => probes.push(probe),
+            }
+        }
+        probes.extend(self.probes_append);
+        Ok(())
+    }
 }
 
 #[derive(Error, Debug)]
@@ -149,22 +660,24 @@ fn default_block_height() -> u16 {
     1
 }
 
-/// Color-block grid options (neofetch `block_range`/`block_width`/
-/// `block_height`/`col_offset`).
-#[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct ColorBlocks {
-    /// Inclusive `[start, end]` palette range to display.
-    #[serde(default = "default_block_range")]
-    pub range: [u8; 2],
-    /// Width of each block, in spaces.
-    #[serde(default = "default_block_width")]
-    pub width: u16,
-    /// Number of rows each block group spans.
-    #[serde(default = "default_block_height")]
-    pub height: u16,
-    /// Left offset before the blocks; `None` = auto (align with the info column).
-    #[serde(default)]
-    pub offset: Option<u16>,
+described! {
+    /// Color-block grid options (neofetch `block_range`/`block_width`/
+    /// `block_height`/`col_offset`).
+    #[derive(Clone, Debug, Deserialize, Serialize)]
+    pub struct ColorBlocks {
+        /// Inclusive `[start, end]` palette range to display.
+        #[serde(default = "default_block_range")]
+        pub range: [u8; 2],
+        /// Width of each block, in spaces.
+        #[serde(default = "default_block_width")]
+        pub width: u16,
+        /// Number of rows each block group spans.
+        #[serde(default = "default_block_height")]
+        pub height: u16,
+        /// Left offset before the blocks; unset = auto (align with the info column).
+        #[serde(default)]
+        pub offset: Option<u16>,
+    }
 }
 
 impl Default for ColorBlocks {
@@ -178,22 +691,98 @@ impl Default for ColorBlocks {
     }
 }
 
+fn default_bar_length() -> u16 {
+    15
+}
+fn default_bar_char_elapsed() -> String {
+    "-".to_string()
+}
+fn default_bar_char_total() -> String {
+    "=".to_string()
+}
+fn default_bar_border() -> bool {
+    true
+}
+
+described! {
+    /// Usage bar style (neofetch `bar_length`/`bar_char_*`/`bar_border`/
+    /// `bar_color_*`), shared by every probe showing a `bar`.
+    #[derive(Clone, Debug, Deserialize, Serialize)]
+    pub struct BarOptions {
+        /// Width in characters, borders excluded.
+        #[serde(default = "default_bar_length")]
+        pub length: u16,
+        /// Character for the used part.
+        #[serde(default = "default_bar_char_elapsed")]
+        pub char_elapsed: String,
+        /// Character for the rest.
+        #[serde(default = "default_bar_char_total")]
+        pub char_total: String,
+        /// Frame the bar in `[` `]`.
+        #[serde(default = "default_bar_border")]
+        pub border: bool,
+        /// 256-colour of the used part; unset = the terminal's foreground.
+        #[serde(default)]
+        pub color_elapsed: Option<u8>,
+        /// 256-colour of the rest; unset = the logo's colour, like neofetch.
+        #[serde(default)]
+        pub color_total: Option<u8>,
+    }
+}
+
+impl Default for BarOptions {
+    fn default() -> Self {
+        Self {
+            length: default_bar_length(),
+            char_elapsed: default_bar_char_elapsed(),
+            char_total: default_bar_char_total(),
+            border: default_bar_border(),
+            color_elapsed: None,
+            color_total: None,
+        }
+    }
+}
+
+impl BarOptions {
+    /// The used and remaining parts of a bar for `ratio` (clamped to
+    /// `0.0..=1.0`), rounding the used part down like neofetch.
+    pub fn segments(&self, ratio: f64) -> (String, String) {
+        let length = usize::from(self.length);
+        let elapsed = ((ratio.clamp(0.0, 1.0) * length as f64) as usize).min(length);
+        (
+            self.char_elapsed.repeat(elapsed),
+            self.char_total.repeat(length - elapsed),
+        )
+    }
+
+    /// The whole bar as plain text, borders included.
+    pub fn render(&self, ratio: f64) -> String {
+        let (elapsed, total) = self.segments(ratio);
+        match self.border {
+            true => format!("[{elapsed}{total}]"),
+            false => format!("{elapsed}{total}"),
+        }
+    }
+}
+
 fn default_ascii_bold() -> bool {
     true
 }
 
-/// ASCII logo options (neofetch `ascii_distro`/`ascii_colors`/`ascii_bold`).
-#[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct AsciiOptions {
-    /// Force a specific distro logo; `None` = auto-detect from the running OS.
-    #[serde(default)]
-    pub distro: Option<String>,
-    /// Override the logo's `${c1}`..`${c6}` palette; empty = the logo's own colours.
-    #[serde(default)]
-    pub colors: Vec<u8>,
-    /// Bold the logo (neofetch defaults this on).
-    #[serde(default = "default_ascii_bold")]
-    pub bold: bool,
+described! {
+    /// ASCII logo options (neofetch `ascii_distro`/`ascii_colors`/`ascii_bold`).
+    #[derive(Clone, Debug, Deserialize, Serialize)]
+    pub struct AsciiOptions {
+        /// Force a specific distro logo; unset = auto-detect from the running OS.
+        #[serde(default)]
+        pub distro: Option<String>,
+        /// Override the logo's `${c1}`..`${c6}` palette; empty = the logo's own colours.
+        #[serde(default)]
+        pub colors: Vec<u8>,
+        /// Bold the logo (neofetch defaults this on).
+        #[serde(default = "default_ascii_bold")]
+        pub bold: bool,
+    }
 }
 
 impl Default for AsciiOptions {
@@ -206,62 +795,95 @@ impl Default for AsciiOptions {
     }
 }
 
-/// Logo backend: ASCII art or a Kitty-graphics-protocol image (neofetch `backend`).
+/// Logo backend: ASCII art or an image (neofetch `backend`).
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Backend {
     #[default]
     Ascii,
     Kitty,
+    /// iTerm2's inline image protocol (iTerm2, WezTerm, Konsole).
+    Iterm2,
+    /// DEC sixel graphics (foot, WezTerm, xterm, mlterm, ...).
+    Sixel,
+    /// `▀`/`▄` half-block cells in the logo's place, for any colour terminal.
+    Blocks,
     /// No logo (neofetch `--off`).
     Off,
 }
 
+impl Backend {
+    /// Whether the backend draws `image_source` rather than ASCII art.
+    pub fn is_image(self) -> bool {
+        matches!(
+            self,
+            Self::Kitty | Self::Iterm2 | Self::Sixel | Self::Blocks
+        )
+    }
+}
+
 fn default_image_cols() -> u16 {
     40
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct NeofetchRendererConfig {
-    /// Whether to display the title
-    /// (e.g. "johndoe@myhostname\n------------------")
-    pub title: bool,
-    pub underline: bool,
-    pub col: bool,
-
-    /// Separator between a label and its value (neofetch `separator`).
-    #[serde(default = "default_separator")]
-    pub separator: String,
-    /// Bold the title and labels (neofetch `bold`).
-    #[serde(default = "default_bold")]
-    pub bold: bool,
-    /// Character used for the title underline (neofetch `underline_char`).
-    #[serde(default = "default_underline_char")]
-    pub underline_char: String,
-    /// Show the fully-qualified hostname in the title (neofetch `title_fqdn`).
-    #[serde(default)]
-    pub title_fqdn: bool,
-    /// 256-colour text slots `[title, @, underline, subtitle, colon, info]`
-    /// (neofetch `colors`). Empty means use the distro's logo colour.
-    #[serde(default)]
-    pub colors: Vec<u8>,
-    /// Color-block grid layout (neofetch `block_*` / `col_offset`).
-    #[serde(default)]
-    pub color_blocks: ColorBlocks,
-    /// ASCII logo options (neofetch `ascii_*`).
-    #[serde(default)]
-    pub ascii: AsciiOptions,
-    /// Logo backend (neofetch `backend`): ASCII art or a Kitty image.
-    #[serde(default)]
-    pub backend: Backend,
-    /// PNG image source for the Kitty backend (neofetch `--source`).
-    #[serde(default)]
-    pub image_source: Option<PathBuf>,
-    /// Image width in terminal cells for the Kitty backend.
-    #[serde(default = "default_image_cols")]
-    pub image_cols: u16,
+fn default_probe_timeout() -> u64 {
+    5000
+}
 
-    pub probes: Vec<ProbeConfig>,
+described! {
+    #[derive(Clone, Debug, Deserialize, Serialize)]
+    pub struct NeofetchRendererConfig {
+        /// Whether to display the title
+        /// (e.g. "johndoe@myhostname\n------------------")
+        pub title: bool,
+        /// Underline the title with `underline_char` (neofetch `underline_enabled`).
+        pub underline: bool,
+        /// Show the colour-block grid below the info (neofetch `col`).
+        pub col: bool,
+
+        /// Separator between a label and its value (neofetch `separator`).
+        #[serde(default = "default_separator")]
+        pub separator: String,
+        /// Bold the title and labels (neofetch `bold`).
+        #[serde(default = "default_bold")]
+        pub bold: bool,
+        /// Character used for the title underline (neofetch `underline_char`).
+        #[serde(default = "default_underline_char")]
+        pub underline_char: String,
+        /// Show the fully-qualified hostname in the title (neofetch `title_fqdn`).
+        #[serde(default)]
+        pub title_fqdn: bool,
+        /// 256-colour text slots `[title, @, underline, subtitle, colon, info]`
+        /// (neofetch `colors`). Empty means use the distro's logo colour.
+        #[serde(default)]
+        pub colors: Vec<u8>,
+        /// Color-block grid layout (neofetch `block_*` / `col_offset`).
+        #[serde(default)]
+        pub color_blocks: ColorBlocks,
+        /// ASCII logo options (neofetch `ascii_*`).
+        #[serde(default)]
+        pub ascii: AsciiOptions,
+        /// Usage bar style for probes with a `bar` (neofetch `bar_*`).
+        #[serde(default)]
+        pub bars: BarOptions,
+        /// Logo backend (neofetch `backend`): ASCII art, a Kitty, iTerm2, sixel or half-block image, or off.
+        #[serde(default)]
+        pub backend: Backend,
+        /// Image source (PNG, JPEG, GIF or WebP) for the image backends (neofetch `--source`).
+        #[serde(default)]
+        pub image_source: Option<PathBuf>,
+        /// Image width in terminal cells for the image backends.
+        #[serde(default = "default_image_cols")]
+        pub image_cols: u16,
+        /// Deadline for each probe in milliseconds (`0` = wait indefinitely). A
+        /// probe still running past it is left out and its subprocesses killed.
+        #[serde(default = "default_probe_timeout")]
+        pub probe_timeout: u64,
+
+        /// Info lines, in display order.
+        #[serde(default = "ProbeConfig::default_neofetch")]
+        pub probes: Vec<ProbeConfig>,
+    }
 }
 
 impl NeofetchRendererConfig {
@@ -286,23 +908,34 @@ impl Default for NeofetchRendererConfig {
             colors: Vec::new(),
             color_blocks: ColorBlocks::default(),
             ascii: AsciiOptions::default(),
+            bars: BarOptions::default(),
             backend: Backend::Ascii,
             image_source: None,
             image_cols: default_image_cols(),
+            probe_timeout: default_probe_timeout(),
             probes: ProbeConfig::default_neofetch(),
         }
     }
 }
 
-/// Configuration for the JSON output renderer.
-#[derive(Clone, Debug, Deserialize, Serialize)]
-pub struct JsonRendererConfig {
-    pub probes: Vec<ProbeConfig>,
+described! {
+    /// Configuration for the JSON output renderer.
+    #[derive(Clone, Debug, Deserialize, Serialize)]
+    pub struct JsonRendererConfig {
+        /// Deadline for each probe in milliseconds (`0` = wait indefinitely); a
+        /// probe still running past it is reported with a `"timed out"` error.
+        #[serde(default = "default_probe_timeout")]
+        pub probe_timeout: u64,
+        /// Probes to report, in output order.
+        #[serde(default = "ProbeConfig::default_all")]
+        pub probes: Vec<ProbeConfig>,
+    }
 }
 
 impl Default for JsonRendererConfig {
     fn default() -> Self {
         Self {
+            probe_timeout: default_probe_timeout(),
             probes: ProbeConfig::default_all(),
         }
     }
@@ -319,17 +952,39 @@ impl Default for JsonRendererConfig {
 // ─────────────────────────────────────────────────────────────────────────
 
 macro_rules! probe_options {
-    ($(#[$m:meta])* $name:ident { $($field:ident : $ty:ty = $default:expr),* $(,)? }) => {
+    ($(#[$m:meta])* $name:ident {
+        $($(#[doc = $doc:literal])* $field:ident : $ty:ty = $default:expr),* $(,)?
+    }) => {
         $(#[$m])*
         #[derive(Clone, Debug, Serialize)]
         pub struct $name {
+            /// Text shown before the value.
             pub label: String,
-            $(pub $field: $ty,)*
+            /// Per-probe deadline in milliseconds, overriding `probe_timeout`
+            /// (`0` = never time out).
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub timeout: Option<u64>,
+            $($(#[doc = $doc])* pub $field: $ty,)*
+        }
+
+        impl Describe for $name {
+            fn kind() -> Kind {
+                Kind::Options(vec![
+                    Field::new("label", "Text shown before the value.", Kind::Str),
+                    Field::new(
+                        "timeout",
+                        "Per-probe deadline in milliseconds, overriding `probe_timeout`\n\
+                         (`0` = never time out).",
+                        <Option<u64> as Describe>::kind(),
+                    ),
+                    $(Field::new(stringify!($field), concat!($($doc, "\n",)* ""), <$ty as Describe>::kind()),)*
+                ])
+            }
         }
 
         impl Default for $name {
             fn default() -> Self {
-                Self { label: String::new(), $($field: $default,)* }
+                Self { label: String::new(), timeout: None, $($field: $default,)* }
             }
         }
 
@@ -355,9 +1010,11 @@ macro_rules! probe_options {
                 // partial table fills the rest from the neofetch defaults.
                 #[derive(Deserialize)]
                 #[serde(default)]
-                struct Full { label: String, $($field: $ty,)* }
+                struct Full { label: String, timeout: Option<u64>, $($field: $ty,)* }
                 impl Default for Full {
-                    fn default() -> Self { Self { label: String::new(), $($field: $default,)* } }
+                    fn default() -> Self {
+                        Self { label: String::new(), timeout: None, $($field: $default,)* }
+                    }
                 }
 
                 struct OptVisitor;
@@ -370,9 +1027,9 @@ macro_rules! probe_options {
                         Ok($name::with_label(s))
                     }
                     fn visit_map<A: serde::de::MapAccess<'de>>(self, map: A) -> Result<$name, A::Error> {
-                        let Full { label, $($field,)* } =
+                        let Full { label, timeout, $($field,)* } =
                             Full::deserialize(serde::de::value::MapAccessDeserializer::new(map))?;
-                        Ok($name { label, $($field,)* })
+                        Ok($name { label, timeout, $($field,)* })
                     }
                 }
                 de.deserialize_any(OptVisitor)
@@ -422,6 +1079,25 @@ pub enum SpeedType {
     ScalingMaxFreq,
 }
 
+/// Temperature unit (`cpu_temp`): Celsius or Fahrenheit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TempUnit {
+    #[default]
+    C,
+    F,
+}
+
+impl TempUnit {
+    /// Render a Celsius reading in this unit to one decimal, e.g. "49.2°C".
+    pub fn format(self, celsius: f64) -> String {
+        match self {
+            TempUnit::C => format!("{celsius:.1}°C"),
+            TempUnit::F => format!("{:.1}°F", celsius * 9.0 / 5.0 + 32.0),
+        }
+    }
+}
+
 /// GPU filter (`gpu_type`).
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -463,6 +1139,57 @@ pub enum DiskSubtitle {
     None,
 }
 
+/// What the Bluetooth line lists (`mode`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BluetoothMode {
+    /// One line per adapter, with its powered state.
+    Adapters,
+    /// One line per connected device.
+    #[default]
+    Devices,
+}
+
+/// Where a usage bar goes (neofetch `memory_display`, `disk_display`, ...).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BarDisplay {
+    /// Text only.
+    #[default]
+    Off,
+    /// The bar instead of the text.
+    Bar,
+    /// The text, then the bar.
+    Infobar,
+    /// The bar, then the text.
+    Barinfo,
+}
+
+/// Enum options are described by the spellings serde accepts for them.
+macro_rules! describe_enums {
+    ($($ty:ty),* $(,)?) => {$(
+        impl Describe for $ty {
+            fn kind() -> Kind {
+                Kind::Enum(meta::serde_names::<$ty>())
+            }
+        }
+    )*};
+}
+describe_enums!(
+    Backend,
+    MemoryUnit,
+    UptimeFormat,
+    CoresMode,
+    SpeedType,
+    TempUnit,
+    GpuType,
+    PackageDisplay,
+    Shorthand,
+    DiskSubtitle,
+    BluetoothMode,
+    BarDisplay,
+);
+
 probe_options!(
     /// A probe with no neofetch options beyond its label.
     LabeledOptions {}
@@ -470,78 +1197,209 @@ probe_options!(
 probe_options!(
     /// Options for the OS/distro line (`distro_shorthand`, `os_arch`).
     DistroOptions {
+        /// How much of the distro name to show: `on` drops the codename,
+        /// `tiny` keeps only the name.
         shorthand: Shorthand = Shorthand::Off,
+        /// Append the machine architecture.
         os_arch: bool = true,
     }
 );
 probe_options!(
     /// Options for the kernel line (`kernel_shorthand`).
-    KernelOptions { shorthand: bool = true }
+    KernelOptions {
+        /// Show only the version, without the kernel name.
+        shorthand: bool = true,
+    }
 );
 probe_options!(
     /// Options for the uptime line (`uptime_shorthand`).
-    UptimeOptions { format: UptimeFormat = UptimeFormat::On }
+    UptimeOptions {
+        /// `on` = "2 days, 3 hours, 4 mins", `tiny` = "2d 3h 4m",
+        /// `off` = "2 days, 3 hours, 4 minutes".
+        format: UptimeFormat = UptimeFormat::On,
+    }
 );
 probe_options!(
     /// Options for the packages line (`package_managers`).
-    PackagesOptions { display: PackageDisplay = PackageDisplay::On }
+    PackagesOptions {
+        /// `on` = a count per manager, `tiny` = the total with manager names,
+        /// `off` = the bare total.
+        display: PackageDisplay = PackageDisplay::On,
+    }
 );
 probe_options!(
     /// Options for the shell line (`shell_path`, `shell_version`).
     ShellOptions {
+        /// Show the full `$SHELL` path instead of the shell name.
         path: bool = false,
+        /// Append the shell version.
         version: bool = true,
     }
 );
 probe_options!(
     /// Options for the resolution line (`refresh_rate`).
-    ResolutionOptions { refresh_rate: bool = false }
+    ResolutionOptions {
+        /// Append the refresh rate, e.g. "2560x1440 @ 165Hz".
+        refresh_rate: bool = false,
+    }
 );
 probe_options!(
     /// Options for the desktop-environment line (`de_version`).
-    DeOptions { version: bool = true }
+    DeOptions {
+        /// Append the desktop environment's version.
+        version: bool = true,
+    }
 );
 probe_options!(
     /// Options for the CPU line (`cpu_brand`, `cpu_cores`, `cpu_speed`, …).
+    /// `temp` appends the package temperature in that unit (`cpu_temp`);
+    /// unset = off, like neofetch.
     CpuOptions {
+        /// Keep the vendor name (e.g. "AMD", "Intel").
         brand: bool = true,
+        /// Which core count to append.
         cores: CoresMode = CoresMode::Logical,
+        /// Append the CPU frequency.
         speed: bool = true,
+        /// sysfs frequency to report.
         speed_type: SpeedType = SpeedType::BiosLimit,
+        /// Round the frequency to one decimal (e.g. "5.1" rather than "5.053").
         speed_shorthand: bool = false,
+        /// Append the package temperature in this unit.
+        temp: Option<TempUnit> = None,
+    }
+);
+probe_options!(
+    /// Options for the standalone CPU temperature line (unit: `c` or `f`).
+    CpuTempOptions {
+        /// Temperature unit.
+        unit: TempUnit = TempUnit::C,
     }
 );
 probe_options!(
     /// Options for the GPU line (`gpu_brand`, `gpu_type`).
     GpuOptions {
+        /// Keep the vendor name (e.g. "NVIDIA").
         brand: bool = true,
+        /// Which GPUs to list.
         gpu_type: GpuType = GpuType::All,
     }
 );
 probe_options!(
     /// Options for the memory line (`memory_unit`, `memory_percent`).
     MemoryOptions {
+        /// Unit for used/total memory.
         unit: MemoryUnit = MemoryUnit::Mib,
+        /// Append the percentage in use.
         percent: bool = false,
+        /// Usage bar: instead of (`bar`), after (`infobar`) or before
+        /// (`barinfo`) the text.
+        bar: BarDisplay = BarDisplay::Off,
     }
 );
 probe_options!(
     /// Options for the disk line (`disk_show`, `disk_subtitle`, `disk_percent`).
     /// Empty `show` means every mount point (neofetch defaults to `/`).
     DiskOptions {
+        /// Mount points to show; empty = every mount point.
         show: Vec<String> = Vec::new(),
+        /// Prefix each line with the mount point, device name or directory name.
         subtitle: DiskSubtitle = DiskSubtitle::Mount,
+        /// Append the percentage in use.
         percent: bool = true,
+        /// Usage bar: instead of (`bar`), after (`infobar`) or before
+        /// (`barinfo`) the text.
+        bar: BarDisplay = BarDisplay::Off,
+    }
+);
+probe_options!(
+    /// Options for the CPU usage line (`cpu_display`).
+    CpuUsageOptions {
+        /// Usage bar: instead of (`bar`), after (`infobar`) or before
+        /// (`barinfo`) the text.
+        bar: BarDisplay = BarDisplay::Off,
+    }
+);
+probe_options!(
+    /// Options for the battery line (`battery_display`).
+    BatteryOptions {
+        /// Charge bar: instead of (`bar`), after (`infobar`) or before
+        /// (`barinfo`) the text.
+        bar: BarDisplay = BarDisplay::Off,
     }
 );
 probe_options!(
     /// Options for the now-playing line (`music_player`, `song_format`, `song_shorthand`).
     SongOptions {
+        /// Player to query (an MPRIS name), or `auto` for the first one playing.
         player: String = "auto".to_string(),
+        /// Line format; `%artist%`, `%album%` and `%title%` are substituted.
         format: String = "%artist% - %album% - %title%".to_string(),
+        /// Use "%artist% - %title%", dropping the album.
         shorthand: bool = false,
     }
 );
+probe_options!(
+    /// Options for a user-defined line: run `command` with `args` (through
+    /// `sh -c` when `shell` is set, so pipes and `$(...)` work) and show its
+    /// stdout. Only the first line is shown unless `multiline` is set, in which
+    /// case each output line gets its own row.
+    CommandOptions {
+        /// Program to run (or a shell snippet, with `shell`).
+        command: String = String::new(),
+        /// Arguments passed to `command` (`$1`, `$2`, … with `shell`).
+        args: Vec<String> = Vec::new(),
+        /// Run through `sh -c`, so pipes and `$(...)` work.
+        shell: bool = false,
+        /// Show every output line on its own row instead of just the first.
+        multiline: bool = false,
+    }
+);
+probe_options!(
+    /// Options for the network line. `show` filters interfaces by name or glob
+    /// (e.g. `"en*"`; empty = every interface); loopback, virtual (no backing
+    /// device: bridges, veth, tun) and down interfaces are hidden by default.
+    NetworkOptions {
+        /// Interface names or globs to show (e.g. `"en*"`); empty = every interface.
+        show: Vec<String> = Vec::new(),
+        /// Include the loopback interface.
+        loopback: bool = false,
+        /// Include virtual interfaces (bridges, veth, tun).
+        virtual_interfaces: bool = false,
+        /// Include interfaces that are down.
+        down: bool = false,
+        /// Append the MAC address.
+        mac: bool = false,
+        /// Append the MTU.
+        mtu: bool = false,
+    }
+);
+probe_options!(
+    /// Options for the Bluetooth line (BlueZ): list adapters or connected
+    /// devices, optionally with each device's battery level.
+    BluetoothOptions {
+        /// List adapters or connected devices.
+        mode: BluetoothMode = BluetoothMode::Devices,
+        /// Append each device's battery level, when it reports one.
+        battery: bool = true,
+    }
+);
+probe_options!(
+    /// Options for the BIOS/firmware line: which DMI fields to show, plus the
+    /// UEFI-vs-legacy boot mode and Secure Boot state.
+    BiosOptions {
+        /// Show the firmware vendor.
+        vendor: bool = true,
+        /// Show the firmware version.
+        version: bool = true,
+        /// Show the release date.
+        date: bool = true,
+        /// Show whether the system booted via UEFI or legacy BIOS.
+        boot_mode: bool = true,
+        /// Show the Secure Boot state (UEFI only).
+        secure_boot: bool = true,
+    }
+);
 
 // ── Per-probe value formatting ───────────────────────────────────────────
 
@@ -807,6 +1665,112 @@ impl DiskOptions {
     }
 }
 
+impl ResolutionOptions {
+    /// Render a display `mode`, appending " @ <Hz>Hz" (rounded) when
+    /// `refresh_rate` is on and the rate is known, e.g. "2560x1440 @ 165Hz".
+    pub fn format(&self, mode: &str, hz: Option<f64>) -> String {
+        match hz {
+            Some(hz) if self.refresh_rate => format!("{mode} @ {}Hz", hz.round() as u64),
+            _ => mode.to_string(),
+        }
+    }
+}
+
+impl NetworkOptions {
+    /// Whether interface `name` passes the `show` filter (exact name or glob).
+    pub fn shows(&self, name: &str) -> bool {
+        self.show.is_empty() || self.show.iter().any(|p| crate::probe::glob_match(p, name))
+    }
+
+    /// Render an interface line: name, then link kind, operstate and speed,
+    /// plus the MTU and MAC address when enabled, e.g.
+    /// "enp5s0 (Wired, up, 2.5 Gbps)".
+    pub fn format(&self, iface: &NetInterface) -> String {
+        let kind = if iface.loopback {
+            "Loopback"
+        } else if iface.wireless {
+            "Wireless"
+        } else {
+            "Wired"
+        };
+        let mut parts = vec![kind.to_string(), iface.state.clone()];
+        if let Some(mbps) = iface.speed_mbps {
+            parts.push(if mbps >= 1000 {
+                format!("{} Gbps", mbps as f64 / 1000.0)
+            } else {
+                format!("{mbps} Mbps")
+            });
+        }
+        if self.mtu
+            && let Some(mtu) = iface.mtu
+        {
+            parts.push(format!("MTU {mtu}"));
+        }
+        if self.mac
+            && let Some(mac) = &iface.mac
+        {
+            parts.push(mac.clone());
+        }
+        format!("{} ({})", iface.name, parts.join(", "))
+    }
+}
+
+impl BiosOptions {
+    /// Render the firmware line, e.g.
+    /// "American Megatrends Inc. 1.A0 (03/12/2024) [UEFI, Secure Boot on]".
+    /// Secure Boot is only reported on UEFI systems where its state is known.
+    pub fn format(&self, info: &BiosInfo) -> String {
+        let mut parts: Vec<String> = Vec::new();
+        if self.vendor
+            && let Some(v) = &info.vendor
+        {
+            parts.push(v.clone());
+        }
+        if self.version
+            && let Some(v) = &info.version
+        {
+            parts.push(v.clone());
+        }
+        if self.date
+            && let Some(d) = &info.date
+        {
+            parts.push(format!("({d})"));
+        }
+        let mut tags: Vec<&str> = Vec::new();
+        if self.boot_mode {
+            tags.push(if info.uefi { "UEFI" } else { "Legacy BIOS" });
+        }
+        if self.secure_boot {
+            match info.secure_boot {
+                Some(true) => tags.push("Secure Boot on"),
+                Some(false) => tags.push("Secure Boot off"),
+                None => {}
+            }
+        }
+        if !tags.is_empty() {
+            parts.push(format!("[{}]", tags.join(", ")));
+        }
+        parts.join(" ")
+    }
+}
+
+impl BluetoothOptions {
+    /// Render an adapter ("desktop (hci0, on)") or a connected device
+    /// ("WH-1000XM4 (80%)", battery shown when BlueZ exposes `Battery1`).
+    pub fn format(&self, item: &BluetoothItem) -> String {
+        match item {
+            BluetoothItem::Adapter { id, name, powered } => {
+                let state = if *powered { "on" } else { "off" };
+                format!("{name} ({id}, {state})")
+            }
+            BluetoothItem::Device { name, battery } => match battery {
+                Some(pct) if self.battery => format!("{name} ({pct}%)"),
+                _ => name.clone(),
+            },
+        }
+    }
+}
+
 /// Probe config. Each variant selects a metric and carries its display label
 /// plus any neofetch-style options. Refer to [`ProbeValue`] for what each
 /// metric corresponds to.
@@ -833,14 +1797,15 @@ pub enum ProbeConfig {
     CPU(CpuOptions),
     GPU(GpuOptions),
     Memory(MemoryOptions),
-    Network(LabeledOptions),
-    Bluetooth(LabeledOptions),
-    BIOS(LabeledOptions),
+    Network(NetworkOptions),
+    Bluetooth(BluetoothOptions),
+    BIOS(BiosOptions),
 
     GPUDriver(LabeledOptions),
-    CPUUsage(LabeledOptions),
+    CPUUsage(CpuUsageOptions),
+    CpuTemp(CpuTempOptions),
     Disk(DiskOptions),
-    Battery(LabeledOptions),
+    Battery(BatteryOptions),
     PowerAdapter(LabeledOptions),
     Font(LabeledOptions),
     Song(SongOptions),
@@ -853,6 +1818,123 @@ pub enum ProbeConfig {
     Python(LabeledOptions),
     Node(LabeledOptions),
     Rust(LabeledOptions),
+
+    /// User-defined command output (neofetch `prin "$(...)"`).
+    Command(CommandOptions),
+
+    // Layout-only entries: rendered in place, nothing is probed.
+    /// A fixed line; with an empty `label` only `value` is shown.
+    Text {
+        #[serde(default)]
+        label: String,
+        value: String,
+    },
+    /// An empty spacer line.
+    Blank,
+    /// A section header in the title colour, optionally underlined with
+    /// `underline_char` like the title.
+    Header {
+        text: String,
+        #[serde(default)]
+        underline: bool,
+    },
+}
+
+impl Describe for ProbeConfig {
+    fn kind() -> Kind {
+        Kind::Probe
+    }
+}
+
+impl ProbeConfig {
+    /// Every variant's config-file name with the shape of its value. Options
+    /// tables also take an `enabled_if`, resolved (and removed) on load.
+    pub fn variant_kinds() -> Vec<(&'static str, Kind)> {
+        let labeled = LabeledOptions::kind;
+        let variants = vec![
+            ("Host", labeled()),
+            ("OS", DistroOptions::kind()),
+            ("Model", labeled()),
+            ("Kernel", KernelOptions::kind()),
+            ("Distro", labeled()),
+            ("Uptime", UptimeOptions::kind()),
+            ("Packages", PackagesOptions::kind()),
+            ("Shell", ShellOptions::kind()),
+            ("Editor", labeled()),
+            ("Resolution", ResolutionOptions::kind()),
+            ("DE", DeOptions::kind()),
+            ("WM", labeled()),
+            ("WMTheme", labeled()),
+            ("Theme", labeled()),
+            ("Icons", labeled()),
+            ("Cursor", labeled()),
+            ("Terminal", labeled()),
+            ("TerminalFont", labeled()),
+            ("CPU", CpuOptions::kind()),
+            ("GPU", GpuOptions::kind()),
+            ("Memory", MemoryOptions::kind()),
+            ("Network", NetworkOptions::kind()),
+            ("Bluetooth", BluetoothOptions::kind()),
+            ("BIOS", BiosOptions::kind()),
+            ("GPUDriver", labeled()),
+            ("CPUUsage", CpuUsageOptions::kind()),
+            ("CpuTemp", CpuTempOptions::kind()),
+            ("Disk", DiskOptions::kind()),
+            ("Battery", BatteryOptions::kind()),
+            ("PowerAdapter", labeled()),
+            ("Font", labeled()),
+            ("Song", SongOptions::kind()),
+            ("LocalIP", labeled()),
+            ("PublicIP", labeled()),
+            ("Users", labeled()),
+            ("Locale", labeled()),
+            ("Java", labeled()),
+            ("Python", labeled()),
+            ("Node", labeled()),
+            ("Rust", labeled()),
+            ("Command", CommandOptions::kind()),
+            (
+                "Text",
+                Kind::Table(vec![
+                    Field::new("label", "Label; empty shows only `value`.", Kind::Str),
+                    Field::new("value", "The line's text.", Kind::Str).required(),
+                ]),
+            ),
+            ("Blank", Kind::Unit),
+            (
+                "Header",
+                Kind::Table(vec![
+                    Field::new("text", "Header text, in the title colour.", Kind::Str).required(),
+                    Field::new(
+                        "underline",
+                        "Underline it with `underline_char`, like the title.",
+                        Kind::Bool,
+                    ),
+                ]),
+            ),
+        ];
+        let enabled_if = || {
+            Field::new(
+                "enabled_if",
+                "Only show this probe where these conditions hold.",
+                Kind::Optional(Box::new(condition::Condition::kind())),
+            )
+        };
+        variants
+            .into_iter()
+            .map(|(name, kind)| match kind {
+                Kind::Options(mut fields) => {
+                    fields.push(enabled_if());
+                    (name, Kind::Options(fields))
+                }
+                Kind::Table(mut fields) => {
+                    fields.push(enabled_if());
+                    (name, Kind::Table(fields))
+                }
+                kind => (name, kind),
+            })
+            .collect()
+    }
 }
 
 impl ProbeConfig {
@@ -878,13 +1960,14 @@ impl ProbeConfig {
             Self::CPU(CpuOptions::with_label("CPU")),
             Self::GPU(GpuOptions::with_label("GPU")),
             Self::Memory(MemoryOptions::with_label("Memory")),
-            Self::Network(LabeledOptions::with_label("Network")),
-            Self::Bluetooth(LabeledOptions::with_label("Bluetooth")),
-            Self::BIOS(LabeledOptions::with_label("BIOS")),
+            Self::Network(NetworkOptions::with_label("Network")),
+            Self::Bluetooth(BluetoothOptions::with_label("Bluetooth")),
+            Self::BIOS(BiosOptions::with_label("BIOS")),
             Self::GPUDriver(LabeledOptions::with_label("GPU Driver")),
-            Self::CPUUsage(LabeledOptions::with_label("CPU Usage")),
+            Self::CPUUsage(CpuUsageOptions::with_label("CPU Usage")),
+            Self::CpuTemp(CpuTempOptions::with_label("CPU Temp")),
             Self::Disk(DiskOptions::with_label("Disk")),
-            Self::Battery(LabeledOptions::with_label("Battery")),
+            Self::Battery(BatteryOptions::with_label("Battery")),
             Self::PowerAdapter(LabeledOptions::with_label("Power Adapter")),
             Self::Font(LabeledOptions::with_label("Font")),
             Self::Song(SongOptions::with_label("Song")),
@@ -951,6 +2034,7 @@ impl ProbeConfig {
             Self::BIOS(o) => &o.label,
             Self::GPUDriver(o) => &o.label,
             Self::CPUUsage(o) => &o.label,
+            Self::CpuTemp(o) => &o.label,
             Self::Disk(o) => &o.label,
             Self::Battery(o) => &o.label,
             Self::PowerAdapter(o) => &o.label,
@@ -964,17 +2048,90 @@ impl ProbeConfig {
             Self::Python(o) => &o.label,
             Self::Node(o) => &o.label,
             Self::Rust(o) => &o.label,
+            Self::Command(o) => &o.label,
+            Self::Text { label, .. } => label,
+            Self::Blank => "",
+            Self::Header { text, .. } => text,
+        }
+    }
+
+    /// Per-probe deadline override in milliseconds, if set.
+    pub fn timeout(&self) -> Option<u64> {
+        match self {
+            Self::Host(o) => o.timeout,
+            Self::OS(o) => o.timeout,
+            Self::Model(o) => o.timeout,
+            Self::Kernel(o) => o.timeout,
+            Self::Distro(o) => o.timeout,
+            Self::Uptime(o) => o.timeout,
+            Self::Packages(o) => o.timeout,
+            Self::Shell(o) => o.timeout,
+            Self::Editor(o) => o.timeout,
+            Self::Resolution(o) => o.timeout,
+            Self::DE(o) => o.timeout,
+            Self::WM(o) => o.timeout,
+            Self::WMTheme(o) => o.timeout,
+            Self::Theme(o) => o.timeout,
+            Self::Icons(o) => o.timeout,
+            Self::Cursor(o) => o.timeout,
+            Self::Terminal(o) => o.timeout,
+            Self::TerminalFont(o) => o.timeout,
+            Self::CPU(o) => o.timeout,
+            Self::GPU(o) => o.timeout,
+            Self::Memory(o) => o.timeout,
+            Self::Network(o) => o.timeout,
+            Self::Bluetooth(o) => o.timeout,
+            Self::BIOS(o) => o.timeout,
+            Self::GPUDriver(o) => o.timeout,
+            Self::CPUUsage(o) => o.timeout,
+            Self::CpuTemp(o) => o.timeout,
+            Self::Disk(o) => o.timeout,
+            Self::Battery(o) => o.timeout,
+            Self::PowerAdapter(o) => o.timeout,
+            Self::Font(o) => o.timeout,
+            Self::Song(o) => o.timeout,
+            Self::LocalIP(o) => o.timeout,
+            Self::PublicIP(o) => o.timeout,
+            Self::Users(o) => o.timeout,
+            Self::Locale(o) => o.timeout,
+            Self::Java(o) => o.timeout,
+            Self::Python(o) => o.timeout,
+            Self::Node(o) => o.timeout,
+            Self::Rust(o) => o.timeout,
+            Self::Command(o) => o.timeout,
+            Self::Text { .. } | Self::Blank | Self::Header { .. } => None,
         }
     }
 
     /// Stable machine-readable key for this probe (used by JSON output).
     pub fn id(&self) -> &'static str {
-        self.probe_type().id()
+        match self {
+            Self::Text { .. } => "text",
+            Self::Blank => "blank",
+            Self::Header { .. } => "header",
+            _ => self.probe_type().map_or("", |t| t.id()),
+        }
     }
 
-    /// The underlying metric this probe gathers.
-    fn probe_type(&self) -> ProbeType {
+    /// Whether this is a layout-only entry (`Text`, `Blank`, `Header`).
+    pub fn is_layout(&self) -> bool {
+        self.probe_type().is_none()
+    }
+
+    /// Where this probe draws a usage bar; `Off` for probes without one.
+    pub fn bar(&self) -> BarDisplay {
         match self {
+            Self::Memory(o) => o.bar,
+            Self::Disk(o) => o.bar,
+            Self::CPUUsage(o) => o.bar,
+            Self::Battery(o) => o.bar,
+            _ => BarDisplay::Off,
+        }
+    }
+
+    /// The underlying metric this probe gathers; `None` for layout entries.
+    fn probe_type(&self) -> Option<ProbeType> {
+        Some(match self {
             Self::Host(_) => ProbeType::Host,
             Self::OS(_) => ProbeType::OS,
             Self::Model(_) => ProbeType::Model,
@@ -1001,6 +2158,7 @@ impl ProbeConfig {
             Self::BIOS(_) => ProbeType::BIOS,
             Self::GPUDriver(_) => ProbeType::GPUDriver,
             Self::CPUUsage(_) => ProbeType::CPUUsage,
+            Self::CpuTemp(_) => ProbeType::CpuTemp,
             Self::Disk(_) => ProbeType::Disk,
             Self::Battery(_) => ProbeType::Battery,
             Self::PowerAdapter(_) => ProbeType::PowerAdapter,
@@ -1014,28 +2172,63 @@ impl ProbeConfig {
             Self::Python(_) => ProbeType::Python,
             Self::Node(_) => ProbeType::Node,
             Self::Rust(_) => ProbeType::Rust,
-        }
+            Self::Command(_) => ProbeType::Command,
+            Self::Text { .. } | Self::Blank | Self::Header { .. } => return None,
+        })
     }
 
     /// Build the `(label, probe function)` pair the renderer executes.
-    pub fn get_funcs(&self) -> (String, ProbeResultFunction) {
-        // Disk/GPU capture their options into the closure (mount filtering and
-        // GPU-type filtering happen at gather time); the rest are option-
-        // independent when gathering and format via `format_value`.
+    /// Build this probe's `(label, function, deadline)` entry. The deadline is
+    /// the probe's own `timeout`, else `default_timeout_ms`; `0` disables it.
+    pub fn get_funcs(
+        &self,
+        default_timeout_ms: u64,
+    ) -> (String, ProbeResultFunction, Option<Duration>) {
+        // CPU/Disk/GPU/Network/Bluetooth/Resolution/Command capture their
+        // options into the closure (whether to read the temperature, mount,
+        // GPU-type, interface, adapter-vs-device, per-display refresh-rate
+        // selection and the command line itself are needed at gather time); the
+        // rest are option-independent when gathering and format via
+        // `format_value`.
         let func: ProbeResultFunction = match self {
+            Self::CPU(o) => crate::probe::cpu_probe_fn(o.clone()),
             Self::Disk(o) => crate::probe::disk_probe_fn(o.clone()),
             Self::GPU(o) => crate::probe::gpu_probe_fn(o.clone()),
             Self::Song(o) => crate::probe::song_probe_fn(o.clone()),
-            _ => self.probe_type().into(),
+            Self::Command(o) => crate::probe::command_probe_fn(o.clone()),
+            Self::Network(o) => crate::probe::network_probe_fn(o.clone()),
+            Self::Bluetooth(o) => crate::probe::bluetooth_probe_fn(o.clone()),
+            Self::Resolution(o) => crate::probe::resolution_probe_fn(o.clone()),
+            // Layout entries resolve immediately to their own text.
+            Self::Text { value, .. } => {
+                let value = value.clone();
+                Arc::new(move || {
+                    Ok(ProbeResultValue::Single(ProbeValue::Text(value.clone())).into())
+                })
+            }
+            Self::Blank | Self::Header { .. } => {
+                let text = self.label().to_string();
+                Arc::new(
+                    move || Ok(ProbeResultValue::Single(ProbeValue::Text(text.clone())).into()),
+                )
+            }
+            _ => self
+                .probe_type()
+                .expect("metric probes have a probe type")
+                .into(),
         };
-        (self.label().to_string(), func)
+        let timeout_ms = self.timeout().unwrap_or(default_timeout_ms);
+        let deadline = (timeout_ms > 0).then(|| Duration::from_millis(timeout_ms));
+        (self.label().to_string(), func, deadline)
     }
 
     /// Render a probed value for this probe, honoring its options. Falls back
     /// to the option-free [`ProbeValue::format`] for probes without options.
     pub fn format_value(&self, value: &ProbeValue) -> String {
         match (self, value) {
-            (Self::CPU(o), ProbeValue::CPU(model)) => crate::probe::format_cpu(model, o),
+            (Self::CPU(o), ProbeValue::CPU(model, temp)) => {
+                crate::probe::format_cpu(model, *temp, o)
+            }
             (Self::OS(o), ProbeValue::OS(name)) => o.format(name),
             (Self::Kernel(o), ProbeValue::Kernel(v)) => o.format(v),
             (Self::Uptime(o), ProbeValue::Uptime(s)) => o.format(*s),
@@ -1047,6 +2240,11 @@ impl ProbeConfig {
                 o.format(mount, name, *used, *total)
             }
             (Self::GPU(o), ProbeValue::GPU(name)) => o.format(name),
+            (Self::Network(o), ProbeValue::Network(iface)) => o.format(iface),
+            (Self::BIOS(o), ProbeValue::BIOS(info)) => o.format(info),
+            (Self::Bluetooth(o), ProbeValue::Bluetooth(item)) => o.format(item),
+            (Self::CpuTemp(o), ProbeValue::CpuTemp(c)) => o.unit.format(*c),
+            (Self::Resolution(o), ProbeValue::Resolution(mode, hz)) => o.format(mode, *hz),
             _ => value.format(),
         }
     }
@@ -1094,6 +2292,290 @@ probes = [
         }
     }
 
+    #[test]
+    fn probe_deadline_defaults_and_overrides() {
+        let src = r#"
+[Json]
+probe_timeout = 1500
+probes = [
+    { OS = "OS" },
+    { Packages = { label = "Packages", timeout = 10000 } },
+    { Java = { label = "Java", timeout = 0 } },
+]
+"#;
+        let Config::Json(c) = toml::from_str(src).expect("deserialize") else {
+            panic!("expected Json");
+        };
+        let deadlines: Vec<_> = c
+            .probes
+            .iter()
+            .map(|p| p.get_funcs(c.probe_timeout).2)
+            .collect();
+        assert_eq!(
+            deadlines,
+            vec![
+                Some(Duration::from_millis(1500)),
+                Some(Duration::from_millis(10000)),
+                None,
+            ]
+        );
+        assert_eq!(JsonRendererConfig::default().probe_timeout, 5000);
+    }
+
+    #[test]
+    fn layout_entries_parse_and_resolve_in_place() {
+        let src = r#"
+[Neofetch]
+title = true
+underline = true
+col = true
+probes = [
+    { Header = { text = "Hardware", underline = true } },
+    { Text = { label = "Rack", value = "B3" } },
+    "Blank",
+    { OS = "OS" },
+]
+"#;
+        let Config::Neofetch(c) = toml::from_str(src).expect("deserialize") else {
+            panic!("expected Neofetch");
+        };
+        let ids: Vec<_> = c.probes.iter().map(ProbeConfig::id).collect();
+        assert_eq!(ids, ["header", "text", "blank", "os"]);
+        assert!(c.probes[..3].iter().all(ProbeConfig::is_layout));
+        assert!(!c.probes[3].is_layout());
+        assert_eq!(c.probes[1].label(), "Rack");
+        let (_, func, _) = c.probes[1].get_funcs(0);
+        let Ok(ProbeResultValue::Single(value)) = func().map(|output| output.value) else {
+            panic!("expected the fixed text");
+        };
+        assert_eq!(c.probes[1].format_value(&value), "B3");
+    }
+
+    #[test]
+    fn extends_preset_with_probe_list_edits() {
+        let src = r#"
+extends = "neofetch"
+
+[Neofetch]
+separator = " ->"
+probes_remove = ["resolution", "gpu"]
+probes_replace = [{ CPU = { label = "Processor", cores = "physical" } }]
+probes_append = [{ Disk = "Disk" }]
+
+[Neofetch.ascii]
+bold = false
+"#;
+        let table = Config::parse_table(src, Path::new("."), &mut Vec::new(), None).expect("load");
+        let cfg = Config::from_table(table, None, false).expect("select");
+        let Config::Neofetch(c) = cfg else {
+            panic!("expected Neofetch");
+        };
+        // Untouched keys are inherited; nested tables merge field by field.
+        assert_eq!(c.separator, " ->");
+        assert!(c.title && c.bold && !c.ascii.bold);
+        assert_eq!(c.ascii.distro, None);
+        let ids: Vec<_> = c.probes.iter().map(ProbeConfig::id).collect();
+        let mut expected: Vec<_> = ProbeConfig::default_neofetch()
+            .iter()
+            .map(ProbeConfig::id)
+            .filter(|id| !["resolution", "gpu"].contains(id))
+            .collect();
+        expected.push("disk");
+        assert_eq!(ids, expected);
+        let cpu = c.probes.iter().find(|p| p.id() == "cpu").expect("cpu kept");
+        assert_eq!(cpu.label(), "Processor");
+    }
+
+    #[test]
+    fn probe_list_edits_reject_ambiguous_ids() {
+        let base = r#"
+extends = "neofetch"
+
+[Neofetch]
+probes = [{ Disk = "Root" }, { Disk = "Home" }, { CPU = "CPU" }]
+"#;
+        let load = |overlay: &str| {
+            let below = Config::parse_table(base, Path::new("."), &mut Vec::new(), None)?;
+            Config::parse_table(overlay, Path::new("."), &mut Vec::new(), Some(below))
+        };
+        for overlay in [
+            "[Neofetch]\nprobes_remove = [\"disk\"]\n",
+            "[Neofetch]\nprobes_replace = [{ Disk = \"Disk\" }]\n",
+        ] {
+            let err = load(overlay).unwrap_err();
+            assert!(matches!(err, ConfigParseError::Invalid(ref m) if m.contains("`disk`")));
+        }
+        // Unique ids still edit in place.
+        let table = load("[Neofetch]\nprobes_remove = [\"cpu\"]\n").expect("load");
+        let Config::Neofetch(c) = Config::from_table(table, None, false).expect("select") else {
+            panic!("expected Neofetch");
+        };
+        let labels: Vec<_> = c.probes.iter().map(ProbeConfig::label).collect();
+        assert_eq!(labels, ["Root", "Home"]);
+    }
+
+    #[test]
+    fn extends_file_chain_and_cycle() {
+        let dir = std::env::temp_dir().join(format!("purr-extends-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("team.toml"),
+            "extends = \"all\"\n[Neofetch]\nseparator = \" =\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("me.toml"),
+            "extends = \"team.toml\"\n[Neofetch]\nbold = false\n",
+        )
+        .unwrap();
+        let Config::Neofetch(c) = Config::from_file(&dir.join("me.toml"), None).unwrap() else {
+            panic!("expected Neofetch");
+        };
+        assert_eq!(c.separator, " =");
+        assert!(!c.bold);
+        assert_eq!(c.probes.len(), ProbeConfig::default_all().len());
+
+        std::fs::write(dir.join("loop.toml"), "extends = \"loop.toml\"\n").unwrap();
+        let err = Config::from_file(&dir.join("loop.toml"), None).unwrap_err();
+        assert!(matches!(err, ConfigParseError::ExtendsCycle(_)));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn layers_build_on_each_other() {
+        let dir = std::env::temp_dir().join(format!("purr-layers-{}", std::process::id()));
+        let (system, user) = (dir.join("etc"), dir.join("user"));
+        std::fs::create_dir_all(system.join("config.d")).unwrap();
+        std::fs::create_dir_all(&user).unwrap();
+        std::fs::write(
+            system.join("config.toml"),
+            "extends = \"neofetch\"\n[Neofetch]\nseparator = \" =\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            system.join("config.d/20-disk.toml"),
+            "[Neofetch]\nprobes_append = [{ Disk = \"Disk\" }]\n",
+        )
+        .unwrap();
+        std::fs::write(
+            system.join("config.d/10-bold.toml"),
+            "[Neofetch]\nbold = false\n",
+        )
+        .unwrap();
+        std::fs::write(system.join("config.d/notes.txt"), "").unwrap();
+        std::fs::write(
+            dir.join("env.toml"),
+            "[Neofetch]\nprobes_remove = [\"gpu\"]\n",
+        )
+        .unwrap();
+
+        let files = Config::layer_files(Some(&system), Some(&user), Some(dir.join("env.toml")));
+        assert_eq!(
+            files,
+            [
+                system.join("config.toml"),
+                system.join("config.d/10-bold.toml"),
+                system.join("config.d/20-disk.toml"),
+                dir.join("env.toml"),
+            ]
+        );
+        let Config::Neofetch(c) = Config::from_files(&files, None).unwrap() else {
+            panic!("expected Neofetch");
+        };
+        assert_eq!(c.separator, " =");
+        assert!(!c.bold);
+        let ids: Vec<_> = c.probes.iter().map(ProbeConfig::id).collect();
+        assert_eq!(ids.last(), Some(&"disk"));
+        assert!(!ids.contains(&"gpu"));
+
+        // Layers that only set a few keys fall back to the defaults.
+        std::fs::write(
+            user.join("config.toml"),
+            "[Neofetch]\nseparator = \" =>\"\n",
+        )
+        .unwrap();
+        let user_only = [user.join("config.toml")];
+        let Config::Neofetch(c) = Config::from_files(&user_only, None).unwrap() else {
+            panic!("expected Neofetch");
+        };
+        assert_eq!(c.separator, " =>");
+        assert!(c.title && c.bold);
+        assert_eq!(c.probes.len(), ProbeConfig::default_neofetch().len());
+        // A higher layer's `extends` goes beneath the layers below it.
+        std::fs::write(
+            dir.join("env.toml"),
+            "extends = \"all\"\n[Neofetch]\nbold = false\n",
+        )
+        .unwrap();
+        let stack = [user.join("config.toml"), dir.join("env.toml")];
+        let Config::Neofetch(c) = Config::from_files(&stack, None).unwrap() else {
+            panic!("expected Neofetch");
+        };
+        assert_eq!(c.separator, " =>");
+        assert!(!c.bold);
+        assert_eq!(c.probes.len(), ProbeConfig::default_all().len());
+        // An explicit file still has to be complete.
+        assert!(Config::from_file(&user.join("config.toml"), None).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn both_renderer_sections_coexist() {
+        let src = r#"
+probes = [{ OS = "OS" }, { Kernel = "Kernel" }]
+
+[Neofetch]
+title = false
+underline = false
+col = false
+separator = " ->"
+
+[Json]
+probe_timeout = 100
+probes = [{ Memory = "Memory" }]
+"#;
+        let load = |target| {
+            let table = Config::parse_table(src, Path::new("."), &mut Vec::new(), None).unwrap();
+            Config::from_table(table, target, false).unwrap()
+        };
+        // No override: Neofetch (present, and the default renderer), with the
+        // shared probe list and its own styling.
+        let Config::Neofetch(n) = load(None) else {
+            panic!("expected Neofetch");
+        };
+        assert_eq!(n.separator, " ->");
+        assert_eq!(n.probes.len(), 2);
+        // `--json` picks the [Json] section with its own probes.
+        let Config::Json(j) = load(Some(RendererOverride::Json)) else {
+            panic!("expected Json");
+        };
+        assert_eq!(j.probe_timeout, 100);
+        assert_eq!(j.probes[0].id(), "memory");
+        // A top-level `renderer` sets the default; an override still wins.
+        let src = format!("renderer = \"json\"\n{src}");
+        let table = Config::parse_table(&src, Path::new("."), &mut Vec::new(), None).unwrap();
+        assert!(matches!(
+            Config::from_table(table, None, false),
+            Ok(Config::Json(_))
+        ));
+        let table = Config::parse_table(&src, Path::new("."), &mut Vec::new(), None).unwrap();
+        let neofetch = Config::from_table(table, Some(RendererOverride::Neofetch), false);
+        assert!(matches!(neofetch, Ok(Config::Neofetch(c)) if c.separator == " ->"));
+
+        // Without a [Neofetch] section, `--neofetch` fills in its defaults
+        // around the [Json] section's probes and timeout.
+        let src = "[Json]\nprobe_timeout = 100\nprobes = [{ Memory = \"Memory\" }]\n";
+        let table = Config::parse_table(src, Path::new("."), &mut Vec::new(), None).unwrap();
+        let Ok(Config::Neofetch(n)) =
+            Config::from_table(table, Some(RendererOverride::Neofetch), false)
+        else {
+            panic!("expected Neofetch");
+        };
+        assert_eq!(n.probe_timeout, 100);
+        assert_eq!(n.probes[0].id(), "memory");
+        assert_eq!(n.separator, NeofetchRendererConfig::default().separator);
+    }
+
     #[test]
     fn memory_units_and_percent() {
         let mut o = MemoryOptions::with_label("Memory");
@@ -1248,4 +2730,131 @@ probes = [
             "42 G / 256 G"
         );
     }
+
+    #[test]
+    fn bios_line_parts() {
+        let info = BiosInfo {
+            vendor: Some("LENOVO".to_string()),
+            version: Some("N32ET91W (1.67)".to_string()),
+            date: Some("05/14/2024".to_string()),
+            uefi: true,
+            secure_boot: Some(false),
+        };
+        let all = BiosOptions::with_label("BIOS");
+        assert_eq!(
+            all.format(&info),
+            "LENOVO N32ET91W (1.67) (05/14/2024) [UEFI, Secure Boot off]"
+        );
+        let version_only = BiosOptions {
+            vendor: false,
+            date: false,
+            boot_mode: false,
+            secure_boot: false,
+            ..Default::default()
+        };
+        assert_eq!(version_only.format(&info), "N32ET91W (1.67)");
+        // Legacy boot has no efivars, so Secure Boot is unknown and omitted.
+        let legacy = BiosInfo {
+            uefi: false,
+            secure_boot: None,
+            ..info
+        };
+        assert_eq!(
+            all.format(&legacy),
+            "LENOVO N32ET91W (1.67) (05/14/2024) [Legacy BIOS]"
+        );
+    }
+
+    #[test]
+    fn bluetooth_adapter_and_device_lines() {
+        let opts = BluetoothOptions::with_label("Bluetooth");
+        let adapter = BluetoothItem::Adapter {
+            id: "hci0".to_string(),
+            name: "desktop".to_string(),
+            powered: true,
+        };
+        assert_eq!(opts.format(&adapter), "desktop (hci0, on)");
+        let headset = BluetoothItem::Device {
+            name: "WH-1000XM4".to_string(),
+            battery: Some(80),
+        };
+        assert_eq!(opts.format(&headset), "WH-1000XM4 (80%)");
+        let no_battery = BluetoothOptions {
+            battery: false,
+            ..Default::default()
+        };
+        assert_eq!(no_battery.format(&headset), "WH-1000XM4");
+    }
+
+    #[test]
+    fn temp_units() {
+        assert_eq!(TempUnit::C.format(49.25), "49.2°C");
+        assert_eq!(TempUnit::F.format(50.0), "122.0°F");
+        // `cpu_temp` on the CPU line parses from the table form.
+        let cfg: Config = toml::from_str(
+            r#"
+[Neofetch]
+title = true
+underline = true
+col = true
+probes = [{ CPU = { label = "CPU", temp = "f" } }]
+"#,
+        )
+        .expect("deserialize");
+        let Config::Neofetch(c) = cfg else {
+            panic!("expected Neofetch")
+        };
+        match &c.probes[0] {
+            ProbeConfig::CPU(o) => assert_eq!(o.temp, Some(TempUnit::F)),
+            other => panic!("expected CPU, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn resolution_refresh_rate() {
+        let on = ResolutionOptions {
+            refresh_rate: true,
+            ..Default::default()
+        };
+        assert_eq!(on.format("2560x1440", Some(164.96)), "2560x1440 @ 165Hz");
+        assert_eq!(on.format("1920x1080", None), "1920x1080");
+        let off = ResolutionOptions::with_label("Resolution");
+        assert_eq!(off.format("2560x1440", Some(165.0)), "2560x1440");
+    }
+
+    #[test]
+    fn network_line_and_filter() {
+        let iface = NetInterface {
+            name: "enp5s0".to_string(),
+            state: "up".to_string(),
+            mac: Some("a8:a1:59:12:34:56".to_string()),
+            speed_mbps: Some(2500),
+            mtu: Some(1500),
+            wireless: false,
+            loopback: false,
+        };
+        let default = NetworkOptions::with_label("Network");
+        assert_eq!(default.format(&iface), "enp5s0 (Wired, up, 2.5 Gbps)");
+        let verbose = NetworkOptions {
+            mac: true,
+            mtu: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            verbose.format(&NetInterface {
+                speed_mbps: Some(100),
+                ..iface
+            }),
+            "enp5s0 (Wired, up, 100 Mbps, MTU 1500, a8:a1:59:12:34:56)"
+        );
+
+        assert!(default.shows("wlan0"));
+        let only_wired = NetworkOptions {
+            show: vec!["en*".to_string(), "eth0".to_string()],
+            ..Default::default()
+        };
+        assert!(only_wired.shows("enp5s0"));
+        assert!(only_wired.shows("eth0"));
+        assert!(!only_wired.shows("wlan0"));
+    }
 }
@@ -0,0 +1,258 @@
+//! Commented config output for `purr generate`.
+//!
+//! Every option is written with its doc comment and, for enum options, the
+//! values it accepts. Both come from the [`Describe`](super::meta::Describe)
+//! impls, i.e. from the config structs' own `///` docs, so the generated
+//! comments follow the code.
+
+use std::fmt::Write as _;
+
+use toml::Value;
+
+use super::{
+    Config,
+    meta::{Field, Kind},
+};
+
+const HEADER: &str = "\
+# purr configuration, generated by `purr generate`. Every option is listed with
+# its default; commented-out options are unset. `purr config check` validates
+# this file after editing.
+#
+# Each probe is either `Name = \"Label\"` or a `[<section>.probes.Name]` table of
+# options. Any probe also takes `timeout` (milliseconds) to override the
+# section's `probe_timeout`.
+";
+
+/// Render `config` as a commented TOML document.
+pub fn to_annotated_string(config: &Config) -> Result<String, toml::ser::Error> {
+    let Value::Table(doc) = Value::try_from(config)? else {
+        unreachable!("a config serializes to a table");
+    };
+    let Kind::Table(sections) = Config::document_kind() else {
+        unreachable!("a config document describes as a table");
+    };
+    let probes = super::ProbeConfig::variant_kinds();
+
+    let mut out = HEADER.to_string();
+    for section in &sections {
+        if let (Some(Value::Table(values)), Kind::Table(fields)) =
+            (doc.get(section.name), unwrap_optional(&section.kind))
+        {
+            out.push('\n');
+            comments(&mut out, section);
+            write_table(&mut out, section.name, values, fields, &probes);
+        }
+    }
+    Ok(out)
+}
+
+fn unwrap_optional(kind: &Kind) -> &Kind {
+    match kind {
+        Kind::Optional(inner) => unwrap_optional(inner),
+        kind => kind,
+    }
+}
+
+/// Write a `[path]` table: plain options first, then sub-tables, then probe
+/// lists (TOML needs a table's own keys before any nested header).
+fn write_table(
+    out: &mut String,
+    path: &str,
+    values: &toml::Table,
+    fields: &[Field],
+    probes: &[(&'static str, Kind)],
+) {
+    let _ = writeln!(out, "[{path}]");
+    let is_probe_list =
+        |f: &Field| matches!(unwrap_optional(&f.kind), Kind::List(item) if **item == Kind::Probe);
+    for field in fields {
+        if !is_probe_list(field) && !matches!(unwrap_optional(&field.kind), Kind::Table(_)) {
+            write_option(out, field, values.get(field.name));
+        }
+    }
+    for field in fields {
+        if let (Kind::Table(sub), Some(Value::Table(sub_values))) =
+            (unwrap_optional(&field.kind), values.get(field.name))
+        {
+            out.push('\n');
+            comments(out, field);
+            write_table(
+                out,
+                &format!("{path}.{}", field.name),
+                sub_values,
+                sub,
+                probes,
+            );
+        }
+    }
+    for field in fields.iter().filter(|f| is_probe_list(f)) {
+        if let Some(Value::Array(items)) = values.get(field.name) {
+            out.push('\n');
+            comments(out, field);
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push('\n');
+                }
+                write_probe(out, &format!("{path}.{}", field.name), item, probes);
+            }
+        }
+    }
+}
+
+/// One `[[path]]` probe entry, each option commented.
+fn write_probe(out: &mut String, path: &str, item: &Value, probes: &[(&'static str, Kind)]) {
+    let _ = writeln!(out, "[[{path}]]");
+    write_probe_entry(out, path, item, |out, name, values| {
+        let kind = probes.iter().find(|(n, _)| *n == name).map(|(_, k)| k);
+        let Some(Kind::Options(fields) | Kind::Table(fields)) = kind else {
+            for (key, value) in values {
+                let _ = writeln!(out, "{key} = {value}");
+            }
+            return;
+        };
+        for field in fields {
+            // Common keys are explained once in the file header.
+            if is_common(field) {
+                if let Some(v) = values.get(field.name) {
+                    let _ = writeln!(out, "{} = {v}", field.name);
+                }
+            } else {
+                write_option(out, field, values.get(field.name));
+            }
+        }
+    });
+}
+
+/// The body of a `[[path]]` probe entry (its header already written), in the
+/// shapes `ProbeConfig` serializes to: the terse `Name = "Label"` form when
+/// the probe has no other options, else a `[path.Name]` table whose
+/// `key = value` lines `options` writes. Also used by `purr config show`.
+pub(super) fn write_probe_entry(
+    out: &mut String,
+    path: &str,
+    item: &Value,
+    options: impl FnOnce(&mut String, &str, &toml::Table),
+) {
+    let (name, body) = match item {
+        Value::String(name) => (name.as_str(), None),
+        Value::Table(t) if t.len() == 1 => {
+            let (name, body) = t.iter().next().expect("one entry");
+            (name.as_str(), Some(body))
+        }
+        other => {
+            // Not a shape `ProbeConfig` serializes to; keep it verbatim.
+            let _ = writeln!(out, "{other}");
+            return;
+        }
+    };
+    match body {
+        Some(Value::Table(values)) => match values.get("label") {
+            Some(label) if values.len() == 1 => {
+                let _ = writeln!(out, "{name} = {label}");
+            }
+            _ => {
+                let _ = writeln!(out, "[{path}.{name}]");
+                options(out, name, values);
+            }
+        },
+        Some(body) => {
+            let _ = writeln!(out, "{name} = {body}");
+        }
+        // Unit variants (`Blank`) as an empty table, the form TOML allows here.
+        None => {
+            let _ = writeln!(out, "{name} = {{}}");
+        }
+    }
+}
+
+/// `label`/`timeout`/`enabled_if`, shared by every probe's options.
+fn is_common(field: &Field) -> bool {
+    matches!(field.name, "label" | "timeout" | "enabled_if")
+}
+
+/// A commented `key = value` line, or a commented-out example when unset.
+fn write_option(out: &mut String, field: &Field, value: Option<&Value>) {
+    match value {
+        Some(value) => {
+            comments(out, field);
+            let _ = writeln!(out, "{} = {value}", field.name);
+        }
+        None if matches!(field.kind, Kind::Optional(_)) => {
+            comments(out, field);
+            let _ = writeln!(out, "# {} = {}", field.name, example(&field.kind));
+        }
+        // Keys only meaningful when editing (e.g. `probes_append`).
+        None => {}
+    }
+}
+
+/// The field's doc comment, plus the accepted values for enum options.
+fn comments(out: &mut String, field: &Field) {
+    for line in field.doc_lines() {
+        let _ = writeln!(out, "# {line}");
+    }
+    if let Kind::Enum(names) = unwrap_optional(&field.kind) {
+        let _ = writeln!(out, "# one of: {}", names.join(", "));
+    }
+}
+
+/// A placeholder value of `kind` for commented-out options.
+fn example(kind: &Kind) -> String {
+    match kind {
+        Kind::Bool => "false".to_string(),
+        Kind::Int { min, .. } => min.to_string(),
+        Kind::Str | Kind::Path => "\"\"".to_string(),
+        Kind::Enum(names) => format!("\"{}\"", names.first().unwrap_or(&"")),
+        Kind::List(_) | Kind::Array(..) => "[]".to_string(),
+        Kind::Optional(inner) => example(inner),
+        Kind::Table(_) | Kind::Map(_) | Kind::Options(_) | Kind::Probe | Kind::Unit => {
+            "{}".to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::check::check_str;
+
+    #[test]
+    fn presets_round_trip_with_comments() {
+        for config in [
+            Config::default_neofetch(),
+            Config::default_all(),
+            Config::default_json(),
+        ] {
+            let annotated = to_annotated_string(&config).unwrap();
+            assert_eq!(check_str(&annotated, false), []);
+            let reparsed: Config = toml::from_str(&annotated).unwrap();
+            assert_eq!(
+                toml::to_string(&reparsed).unwrap(),
+                toml::to_string(&config).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn options_carry_docs_and_allowed_values() {
+        let annotated = to_annotated_string(&Config::default_all()).unwrap();
+        assert!(annotated.contains(
+            "[Neofetch.probes.Memory]\n\
+             label = \"Memory\"\n\
+             # Unit for used/total memory.\n\
+             # one of: kib, mib, gib\n\
+             unit = \"mib\"\n"
+        ));
+        assert!(annotated.contains("[[Neofetch.probes]]\nModel = \"Host\"\n"));
+        assert!(annotated.contains(
+            "# Force a specific distro logo; unset = auto-detect from the running OS.\n\
+             # distro = \"\"\n"
+        ));
+        assert!(
+            annotated.contains(
+                "# one of: ascii, kitty, iterm2, sixel, blocks, off\nbackend = \"ascii\"\n"
+            )
+        );
+    }
+}
@@ -0,0 +1,496 @@
+//! `purr config check`: validate a config file without running any probes.
+//!
+//! The loader ignores keys it does not know and stops at the first bad value;
+//! this walks the whole document against [`Config::document_kind`] instead,
+//! keeping source spans so every problem is reported with its line and
+//! column, plus a "did you mean" for misspelt keys, probes and values.
+
+use std::{fmt, ops::Range, path::PathBuf};
+
+use toml::{
+    Spanned,
+    de::{DeArray, DeTable, DeValue},
+};
+
+use super::{
+    Config, ProbeConfig,
+    meta::{Field, Kind},
+};
+
+/// Probes that are meant to appear more than once in a list.
+const REPEATABLE: [&str; 4] = ["Command", "Text", "Blank", "Header"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// One problem found in a config file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// 1-based `(line, column)`, when the problem has a place in the file.
+    pub position: Option<(usize, usize)>,
+    pub message: String,
+    /// Likely intended spelling, for a misspelt key or value.
+    pub suggestion: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some((line, column)) = self.position {
+            write!(f, "{line}:{column}: ")?;
+        }
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        write!(f, "{severity}: {}", self.message)?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, " (did you mean `{suggestion}`?)")?;
+        }
+        Ok(())
+    }
+}
+
+/// Check config files, each on its own: the `layered` search path (see
+/// [`Config::config_files`]), else one complete file. Once every file is clean
+/// they are also loaded for real, which catches what a single file cannot show
+/// (a broken `extends` target, a missing required key); such an error is
+/// reported against the last file.
+pub fn check_files(paths: &[PathBuf], layered: bool) -> std::io::Result<Vec<Vec<Diagnostic>>> {
+    let mut reports = paths
+        .iter()
+        .map(|path| Ok(check_str(&std::fs::read_to_string(path)?, layered)))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    let load = || match paths {
+        [path] if !layered => Config::from_file(path, None),
+        _ => Config::from_files(paths, None),
+    };
+    if !reports.iter().flatten().any(Diagnostic::is_error)
+        && let Err(e) = load()
+        && let Some(last) = reports.last_mut()
+    {
+        last.push(Diagnostic {
+            severity: Severity::Error,
+            position: None,
+            message: e.to_string().trim_end().to_string(),
+            suggestion: None,
+        });
+    }
+    Ok(reports)
+}
+
+/// Check a config document's text. A `layered` file (see [`check_files`]) or
+/// one that `extends` something only needs the keys it changes; any other has
+/// to be complete.
+pub fn check_str(src: &str, layered: bool) -> Vec<Diagnostic> {
+    let mut checker = Checker {
+        src,
+        probes: ProbeConfig::variant_kinds(),
+        diagnostics: Vec::new(),
+    };
+    match DeTable::parse(src) {
+        Ok(doc) => {
+            let extends = doc.get_ref().keys().any(|k| k.get_ref() == "extends");
+            let kind = if layered || extends {
+                Config::layer_kind()
+            } else {
+                Config::document_kind()
+            };
+            let Kind::Table(fields) = kind else {
+                unreachable!("a config document describes as a table");
+            };
+            checker.table(doc.get_ref(), doc.span(), &fields, "");
+            checker.block_range(doc.get_ref());
+        }
+        Err(e) => checker.push(
+            Severity::Error,
+            e.span(),
+            e.message().trim_end().to_string(),
+            None,
+        ),
+    }
+    // Tables iterate in key order; report in file order instead.
+    checker
+        .diagnostics
+        .sort_by_key(|d| d.position.unwrap_or((usize::MAX, 0)));
+    checker.diagnostics
+}
+
+struct Checker<'a> {
+    src: &'a str,
+    probes: Vec<(&'static str, Kind)>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Checker<'_> {
+    fn push(
+        &mut self,
+        severity: Severity,
+        span: Option<Range<usize>>,
+        message: String,
+        suggestion: Option<&str>,
+    ) {
+        self.diagnostics.push(Diagnostic {
+            severity,
+            position: span.map(|s| self.position(s.start)),
+            message,
+            suggestion: suggestion.map(str::to_string),
+        });
+    }
+
+    /// 1-based line and column (in characters) of a byte offset.
+    fn position(&self, offset: usize) -> (usize, usize) {
+        let before = &self.src[..offset.min(self.src.len())];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        (
+            before.matches('\n').count() + 1,
+            before[line_start..].chars().count() + 1,
+        )
+    }
+
+    fn table(&mut self, table: &DeTable<'_>, span: Range<usize>, fields: &[Field], path: &str) {
+        for field in fields.iter().filter(|f| f.required) {
+            if !table.keys().any(|k| k.get_ref() == field.name) {
+                self.push(
+                    Severity::Error,
+                    Some(span.clone()),
+                    format!("`{path}` is missing `{}`", field.name),
+                    None,
+                );
+            }
+        }
+        for (key, value) in table {
+            let name = key.get_ref().as_ref();
+            match fields.iter().find(|f| f.name == name) {
+                Some(field) => self.value(value, &field.kind, &join(path, name)),
+                None => {
+                    let place = match path {
+                        "" => "at the top level".to_string(),
+                        _ => format!("in `{path}`"),
+                    };
+                    self.push(
+                        Severity::Error,
+                        Some(key.span()),
+                        format!("unknown key `{name}` {place}"),
+                        suggest(name, fields.iter().map(|f| f.name)),
+                    );
+                }
+            }
+        }
+    }
+
+    fn value(&mut self, value: &Spanned<DeValue<'_>>, kind: &Kind, path: &str) {
+        match (kind, value.get_ref()) {
+            (Kind::Optional(inner), _) => self.value(value, inner, path),
+            (Kind::Bool, DeValue::Boolean(_))
+            | (Kind::Str | Kind::Path, DeValue::String(_))
+            | (Kind::Options(_), DeValue::String(_)) => {}
+            (Kind::Int { min, max }, DeValue::Integer(int)) => {
+                let n = i64::from_str_radix(int.as_str(), int.radix()).ok();
+                if !n.is_some_and(|n| (*min..=*max).contains(&n)) {
+                    self.push(
+                        Severity::Error,
+                        Some(value.span()),
+                        format!("`{path}` is out of range (expected {min}..={max})"),
+                        None,
+                    );
+                }
+            }
+            (Kind::Enum(names), DeValue::String(s)) => {
+                if !names.contains(&s.as_ref()) {
+                    self.push(
+                        Severity::Error,
+                        Some(value.span()),
+                        format!(
+                            "invalid value `{s}` for `{path}` (expected one of: {})",
+                            names.join(", ")
+                        ),
+                        suggest(s, names.iter().copied()),
+                    );
+                }
+            }
+            (Kind::List(item), DeValue::Array(items)) if **item == Kind::Probe => {
+                self.probe_list(items, path);
+            }
+            (Kind::List(item), DeValue::Array(items)) => {
+                for (i, v) in items.iter().enumerate() {
+                    self.value(v, item, &format!("{path}[{i}]"));
+                }
+            }
+            (Kind::Array(len, item), DeValue::Array(items)) => {
+                if items.len() != *len {
+                    self.push(
+                        Severity::Error,
+                        Some(value.span()),
+                        format!("`{path}` takes exactly {len} values, found {}", items.len()),
+                        None,
+                    );
+                }
+                for (i, v) in items.iter().enumerate() {
+                    self.value(v, item, &format!("{path}[{i}]"));
+                }
+            }
+            (Kind::Table(fields) | Kind::Options(fields), DeValue::Table(table)) => {
+                self.table(table, value.span(), fields, path);
+            }
+            (Kind::Map(item), DeValue::Table(table)) => {
+                for (key, v) in table {
+                    self.value(v, item, &format!("{path}.\"{}\"", key.get_ref()));
+                }
+            }
+            (Kind::Probe, _) => {
+                self.probe(value, path);
+            }
+            (kind, found) => self.push(
+                Severity::Error,
+                Some(value.span()),
+                format!(
+                    "`{path}` should be {}, found {}",
+                    expected(kind),
+                    found.type_str()
+                ),
+                None,
+            ),
+        }
+    }
+
+    /// Check a probe list, warning about probes listed more than once.
+    fn probe_list(&mut self, items: &DeArray<'_>, path: &str) {
+        let mut seen: Vec<(&str, usize)> = Vec::new();
+        for (i, item) in items.iter().enumerate() {
+            let Some(name) = self.probe(item, &format!("{path}[{i}]")) else {
+                continue;
+            };
+            if REPEATABLE.contains(&name) {
+                continue;
+            }
+            match seen.iter().find(|(n, _)| *n == name) {
+                Some((_, first)) => {
+                    let (line, _) = self.position(*first);
+                    self.push(
+                        Severity::Warning,
+                        Some(item.span()),
+                        format!(
+                            "duplicate probe `{name}` in `{path}` (first listed on line {line})"
+                        ),
+                        None,
+                    );
+                }
+                None => seen.push((name, item.span().start)),
+            }
+        }
+    }
+
+    /// Check one probe entry (`"Blank"` or `{ Name = … }`), returning its name.
+    fn probe(&mut self, value: &Spanned<DeValue<'_>>, path: &str) -> Option<&'static str> {
+        let (name, span, body) = match value.get_ref() {
+            DeValue::String(name) => (name.as_ref(), value.span(), None),
+            DeValue::Table(table) if table.len() == 1 => {
+                let (key, body) = table.iter().next()?;
+                (key.get_ref().as_ref(), key.span(), Some(body))
+            }
+            found => {
+                self.push(
+                    Severity::Error,
+                    Some(value.span()),
+                    format!(
+                        "`{path}` should be a probe such as `{{ Memory = \"Memory\" }}`, found {}",
+                        if found.is_table() {
+                            "a table without exactly one key"
+                        } else {
+                            found.type_str()
+                        }
+                    ),
+                    None,
+                );
+                return None;
+            }
+        };
+        let Some((variant, kind)) = self.probes.iter().find(|(n, _)| *n == name).cloned() else {
+            self.push(
+                Severity::Error,
+                Some(span),
+                format!("unknown probe `{name}` in `{path}`"),
+                suggest(name, self.probes.iter().map(|(n, _)| *n)),
+            );
+            return None;
+        };
+        match (body, &kind) {
+            (_, Kind::Unit) => {}
+            (None, _) => self.push(
+                Severity::Error,
+                Some(span),
+                format!("probe `{name}` needs a value, e.g. `{{ {name} = \"{name}\" }}`"),
+                None,
+            ),
+            (Some(body), kind) => self.value(body, kind, &format!("{path}.{name}")),
+        }
+        Some(variant)
+    }
+
+    /// `color_blocks.range` must run upwards: `[start, end]` with start <= end.
+    fn block_range(&mut self, doc: &DeTable<'_>) {
+        let range = doc
+            .get("Neofetch")
+            .and_then(|s| s.get_ref().get("color_blocks"))
+            .and_then(|b| b.get_ref().get("range"));
+        let Some(range) = range else { return };
+        let ints: Vec<i64> = range
+            .get_ref()
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|v| v.get_ref().as_integer())
+            .filter_map(|i| i64::from_str_radix(i.as_str(), i.radix()).ok())
+            .collect();
+        if let [start, end] = ints[..]
+            && start > end
+        {
+            self.push(
+                Severity::Error,
+                Some(range.span()),
+                format!("`Neofetch.color_blocks.range` starts after it ends ({start} > {end})"),
+                Some(&format!("[{end}, {start}]")),
+            );
+        }
+    }
+}
+
+fn join(path: &str, key: &str) -> String {
+    match path {
+        "" => key.to_string(),
+        _ => format!("{path}.{key}"),
+    }
+}
+
+/// What a value of `kind` looks like, for type mismatch messages.
+fn expected(kind: &Kind) -> String {
+    match kind {
+        Kind::Bool => "a boolean".to_string(),
+        Kind::Int { .. } => "an integer".to_string(),
+        Kind::Str => "a string".to_string(),
+        Kind::Path => "a path string".to_string(),
+        Kind::Enum(names) => format!("one of: {}", names.join(", ")),
+        Kind::List(_) => "an array".to_string(),
+        Kind::Array(len, _) => format!("an array of {len} values"),
+        Kind::Optional(inner) => expected(inner),
+        Kind::Table(_) | Kind::Map(_) => "a table".to_string(),
+        Kind::Options(_) => "a label string or an options table".to_string(),
+        Kind::Probe => "a probe".to_string(),
+        Kind::Unit => "a bare name".to_string(),
+    }
+}
+
+/// The candidate closest to a misspelt `name`, if any is close enough: one
+/// edit away (short values like `gb` for `gib`) or similar overall.
+fn suggest<'c>(name: &str, candidates: impl Iterator<Item = &'c str>) -> Option<&'c str> {
+    let name = name.to_lowercase();
+    candidates
+        .filter_map(|c| {
+            let c_lower = c.to_lowercase();
+            let score = strsim::jaro_winkler(&name, &c_lower);
+            (score > 0.8 || strsim::levenshtein(&name, &c_lower) <= 1).then_some((score, c))
+        })
+        .max_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, c)| c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(src: &str) -> Vec<String> {
+        check_str(src, false)
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn reports_positions_and_suggestions() {
+        let src = r#"
+renderer = "json"
+
+[Neofetch]
+title = true
+underline = true
+col = true
+colour_blocks = { width = 3 }
+color_blocks = { range = [15, 300] }
+probes = [
+    { Memory = { label = "Memory", unit = "gb" } },
+    { Memroy = "RAM" },
+    { Memory = "Memory again" },
+    { Command = { command = "date" } },
+    { Command = { command = "uptime" } },
+    "Blank",
+    "Kernel",
+    { Header = { underline = true } },
+]
+"#;
+        assert_eq!(
+            render(src),
+            [
+                "8:1: error: unknown key `colour_blocks` in `Neofetch` (did you mean `color_blocks`?)",
+                "9:31: error: `Neofetch.color_blocks.range[1]` is out of range (expected 0..=255)",
+                "11:43: error: invalid value `gb` for `Neofetch.probes[0].Memory.unit` \
+                 (expected one of: kib, mib, gib) (did you mean `gib`?)",
+                "12:7: error: unknown probe `Memroy` in `Neofetch.probes[1]` (did you mean `Memory`?)",
+                "13:5: warning: duplicate probe `Memory` in `Neofetch.probes` (first listed on line 11)",
+                "17:5: error: probe `Kernel` needs a value, e.g. `{ Kernel = \"Kernel\" }`",
+                "18:16: error: `Neofetch.probes[7].Header` is missing `text`",
+            ]
+        );
+    }
+
+    #[test]
+    fn reversed_block_range_and_bad_types() {
+        let src = "extends = 3\n[Neofetch.color_blocks]\nrange = [15, 0]\nwidth = \"3\"\n";
+        assert_eq!(
+            render(src),
+            [
+                "1:11: error: `extends` should be a string, found integer",
+                "3:9: error: `Neofetch.color_blocks.range` starts after it ends (15 > 0) \
+                 (did you mean `[0, 15]`?)",
+                "4:9: error: `Neofetch.color_blocks.width` should be an integer, found string",
+            ]
+        );
+    }
+
+    #[test]
+    fn complete_files_need_keys_without_defaults() {
+        let src = "[Neofetch]\nseparator = \" ->\"\n";
+        assert_eq!(
+            render(src),
+            [
+                "1:1: error: `Neofetch` is missing `title`",
+                "1:1: error: `Neofetch` is missing `underline`",
+                "1:1: error: `Neofetch` is missing `col`",
+            ]
+        );
+        // On top of a preset or lower layers, the rest is inherited.
+        assert!(render(&format!("extends = \"neofetch\"\n{src}")).is_empty());
+        assert_eq!(check_str(src, true), []);
+    }
+
+    #[test]
+    fn shipped_presets_are_clean() {
+        for config in [
+            Config::default_neofetch(),
+            Config::default_all(),
+            Config::default_json(),
+        ] {
+            let src = toml::to_string(&config).unwrap();
+            assert_eq!(check_str(&src, false), []);
+        }
+    }
+}
@@ -0,0 +1,351 @@
+//! Host- and environment-conditional config: `[when.<key>."<pattern>"]`
+//! sections and per-probe `enabled_if` tables.
+//!
+//! Both are resolved while a document is loaded, before it is merged with what
+//! it `extends`, so one dotfiles config can carry every machine's variations:
+//!
+//! ```toml
+//! [[Neofetch.probes]]
+//! Battery = { label = "Battery", enabled_if = { host = "laptop-*" } }
+//!
+//! [when.host."srv-*".Neofetch]
+//! probes_replace = [{ Disk = { label = "Disk", show = ["/", "/srv"] } }]
+//! ```
+
+use std::{collections::HashMap, sync::OnceLock};
+
+use serde::Deserialize;
+
+use super::{
+    ConfigParseError, ProbeListOps, RendererOverride, merge_tables,
+    meta::{Describe, Field, Kind},
+};
+use crate::probe::{general_readout, glob_match};
+
+/// The keys a condition can match on, in the order `when` sections apply
+/// (broadest first, so a host section overrides an OS one).
+const KEYS: [&str; 4] = ["os", "distro", "host", "env"];
+
+/// What conditions are matched against.
+#[derive(Clone, Debug, Default)]
+pub struct Facts {
+    pub host: String,
+    /// `std::env::consts::OS` (`linux`, `macos`, `windows`, ...).
+    pub os: String,
+    pub distro: String,
+    pub env: HashMap<String, String>,
+}
+
+impl Facts {
+    /// The running system's, read on first use and kept for the process.
+    pub fn current() -> &'static Self {
+        use libmacchina::traits::GeneralReadout as _;
+        static FACTS: OnceLock<Facts> = OnceLock::new();
+        FACTS.get_or_init(|| Self {
+            host: general_readout().hostname().unwrap_or_default(),
+            os: std::env::consts::OS.to_string(),
+            distro: general_readout().distribution().unwrap_or_default(),
+            env: std::env::vars().collect(),
+        })
+    }
+}
+
+/// A probe's `enabled_if`: every key given has to match.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Condition {
+    host: Option<String>,
+    os: Option<String>,
+    distro: Option<String>,
+    env: Option<String>,
+}
+
+impl Describe for Condition {
+    fn kind() -> Kind {
+        let pattern = || Kind::Optional(Box::new(Kind::Str));
+        Kind::Table(vec![
+            Field::new("host", "Hostname pattern, e.g. `\"build-*\"`.", pattern()),
+            Field::new(
+                "os",
+                "OS pattern: `linux`, `macos`, `windows`, `freebsd`, ...",
+                pattern(),
+            ),
+            Field::new(
+                "distro",
+                "Distribution name pattern, as detected (e.g. `\"Arch*\"`).",
+                pattern(),
+            ),
+            Field::new(
+                "env",
+                "Variable that has to be set and non-empty, or `NAME=pattern`.",
+                pattern(),
+            ),
+        ])
+    }
+}
+
+impl Condition {
+    pub fn matches(&self, facts: &Facts) -> bool {
+        [
+            ("host", &self.host),
+            ("os", &self.os),
+            ("distro", &self.distro),
+            ("env", &self.env),
+        ]
+        .into_iter()
+        .all(|(key, pattern)| pattern.as_ref().is_none_or(|p| matches(key, p, facts)))
+    }
+}
+
+/// Whether the fact `key` matches `pattern`. Patterns are globs (`*`, `?`)
+/// compared case-insensitively; `env` takes `NAME` or `NAME=pattern`.
+fn matches(key: &str, pattern: &str, facts: &Facts) -> bool {
+    match key {
+        "host" => glob(pattern, &facts.host),
+        "os" => glob(pattern, &facts.os),
+        "distro" => glob(pattern, &facts.distro),
+        _ => match pattern.split_once('=') {
+            Some((name, pattern)) => facts.env.get(name).is_some_and(|v| glob(pattern, v)),
+            None => facts.env.get(pattern).is_some_and(|v| !v.is_empty()),
+        },
+    }
+}
+
+/// [`glob_match`], ignoring case.
+fn glob(pattern: &str, text: &str) -> bool {
+    glob_match(&pattern.to_lowercase(), &text.to_lowercase())
+}
+
+/// The shape of `when`: per key, sections keyed by pattern, each holding a
+/// partial document (`document` is the top-level shape without `when`).
+pub fn when_kind(document: &[Field]) -> Kind {
+    let sections = || {
+        Kind::Optional(Box::new(Kind::Map(Box::new(Kind::Table(
+            document.to_vec(),
+        )))))
+    };
+    Kind::Table(vec![
+        Field::new("os", "Sections applied when the OS matches.", sections()),
+        Field::new(
+            "distro",
+            "Sections applied when the distribution matches.",
+            sections(),
+        ),
+        Field::new(
+            "host",
+            "Sections applied when the hostname matches.",
+            sections(),
+        ),
+        Field::new(
+            "env",
+            "Sections applied when a variable is set (`NAME`) or matches\n\
+             (`NAME=pattern`).",
+            sections(),
+        ),
+    ])
+}
+
+/// Resolve a document's conditions against `facts`: merge every matching
+/// `when` section on top (OS, then distro, host and env; sections under one
+/// key by pattern, in sorted order), then drop the probes whose `enabled_if` does not hold.
+/// Probe list edits (`probes_remove` and friends) from several sections add
+/// up rather than replace each other. `facts` is only called once there is a
+/// condition to match, so documents without any never read the system.
+pub fn resolve<'f>(
+    doc: &mut toml::Table,
+    facts: impl Fn() -> &'f Facts,
+) -> Result<(), ConfigParseError> {
+    if let Some(when) = doc.remove("when") {
+        let toml::Value::Table(when) = when else {
+            return Err(invalid("`when` must be a table"));
+        };
+        if let Some(key) = when.keys().find(|k| !KEYS.contains(&k.as_str())) {
+            return Err(invalid(format!(
+                "unknown `when` key `{key}` (expected one of: {})",
+                KEYS.join(", ")
+            )));
+        }
+        for key in KEYS {
+            let Some(sections) = when.get(key) else {
+                continue;
+            };
+            let toml::Value::Table(sections) = sections else {
+                return Err(invalid(format!("`when.{key}` must be a table")));
+            };
+            for (pattern, section) in sections {
+                let toml::Value::Table(section) = section else {
+                    return Err(invalid(format!(
+                        "`when.{key}.\"{pattern}\"` must be a table"
+                    )));
+                };
+                if section.contains_key("when") {
+                    return Err(invalid("`when` sections cannot nest"));
+                }
+                if matches(key, pattern, facts()) {
+                    merge_section(doc, section.clone());
+                }
+            }
+        }
+    }
+    filter_probes(doc, &facts)
+}
+
+/// Merge a matching `when` section, appending its probe list edits to the
+/// document's own.
+fn merge_section(doc: &mut toml::Table, mut section: toml::Table) {
+    for key in RendererOverride::SECTIONS {
+        if let (Some(toml::Value::Table(ours)), Some(toml::Value::Table(theirs))) =
+            (doc.get(key), section.get_mut(key))
+        {
+            for op in ProbeListOps::KEYS {
+                if let (Some(toml::Value::Array(ours)), Some(toml::Value::Array(theirs))) =
+                    (ours.get(op), theirs.get_mut(op))
+                {
+                    theirs.splice(0..0, ours.iter().cloned());
+                }
+            }
+        }
+    }
+    merge_tables(doc, section);
+}
+
+/// Drop probes whose `enabled_if` does not match, and the key from the rest,
+/// in every probe list of the document.
+fn filter_probes<'f>(
+    doc: &mut toml::Table,
+    facts: &impl Fn() -> &'f Facts,
+) -> Result<(), ConfigParseError> {
+    let mut lists: Vec<&mut toml::Value> = Vec::new();
+    for (key, value) in doc.iter_mut() {
+        match (key.as_str(), value) {
+            ("probes", list) => lists.push(list),
+            (key, toml::Value::Table(section)) if RendererOverride::SECTIONS.contains(&key) => {
+                lists.extend(section.iter_mut().filter_map(|(k, v)| {
+                    matches!(k.as_str(), "probes" | "probes_replace" | "probes_append").then_some(v)
+                }));
+            }
+            _ => {}
+        }
+    }
+    for list in lists {
+        let toml::Value::Array(probes) = list else {
+            continue;
+        };
+        let mut kept = Vec::with_capacity(probes.len());
+        for mut probe in probes.drain(..) {
+            if enabled(&mut probe, facts)? {
+                kept.push(probe);
+            }
+        }
+        *probes = kept;
+    }
+    Ok(())
+}
+
+/// Take a probe entry's `enabled_if` (if any) and evaluate it.
+fn enabled<'f>(
+    probe: &mut toml::Value,
+    facts: &impl Fn() -> &'f Facts,
+) -> Result<bool, ConfigParseError> {
+    let options = match probe {
+        toml::Value::Table(entry) if entry.len() == 1 => entry.iter_mut().next().map(|(_, v)| v),
+        _ => None,
+    };
+    let Some(toml::Value::Table(options)) = options else {
+        return Ok(true);
+    };
+    match options.remove("enabled_if") {
+        None => Ok(true),
+        Some(condition) => Ok(condition.try_into::<Condition>()?.matches(facts())),
+    }
+}
+
+fn invalid(message: impl Into<String>) -> ConfigParseError {
+    ConfigParseError::Invalid(message.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn laptop() -> Facts {
+        Facts {
+            host: "laptop-ana".to_string(),
+            os: "linux".to_string(),
+            distro: "Arch Linux".to_string(),
+            env: HashMap::from([("XDG_SESSION_TYPE".to_string(), "wayland".to_string())]),
+        }
+    }
+
+    #[test]
+    fn globs_and_env_patterns() {
+        assert!(glob("build-*", "build-01"));
+        assert!(glob("BUILD-??", "build-01"));
+        assert!(glob("*arch*", "Arch Linux"));
+        assert!(!glob("build-*", "xbuild-01"));
+        assert!(!glob("build-?", "build-01"));
+        let facts = laptop();
+        assert!(matches("env", "XDG_SESSION_TYPE", &facts));
+        assert!(matches("env", "XDG_SESSION_TYPE=way*", &facts));
+        assert!(!matches("env", "XDG_SESSION_TYPE=x11", &facts));
+        assert!(!matches("env", "SSH_CONNECTION", &facts));
+    }
+
+    #[test]
+    fn when_sections_and_enabled_if() {
+        let src = r#"
+[Neofetch]
+separator = ":"
+probes = [
+    { OS = "OS" },
+    { Battery = { label = "Battery", enabled_if = { host = "laptop-*" } } },
+    { Song = { label = "Song", enabled_if = { host = "laptop-*", env = "SSH_CONNECTION" } } },
+]
+probes_append = [{ Memory = "Memory" }]
+
+[when.os.linux.Neofetch]
+separator = " ->"
+
+[when.host."srv-*".Neofetch]
+probes_replace = [{ Disk = { label = "Disk", show = ["/srv"] } }]
+
+[when.env.XDG_SESSION_TYPE.Neofetch]
+probes_append = [{ Resolution = "Resolution" }]
+"#;
+        let resolved = |facts: &Facts| {
+            let mut doc: toml::Table = toml::from_str(src).unwrap();
+            resolve(&mut doc, || facts).unwrap();
+            doc
+        };
+
+        let doc = resolved(&laptop());
+        assert!(!doc.contains_key("when"));
+        let section = doc["Neofetch"].as_table().unwrap();
+        assert_eq!(section["separator"].as_str(), Some(" ->"));
+        // Song needs SSH_CONNECTION too; Battery keeps its options minus the condition.
+        let probes = section["probes"].as_array().unwrap();
+        assert_eq!(probes.len(), 2);
+        assert_eq!(
+            probes[1].to_string(),
+            "{ Battery = { label = \"Battery\" } }"
+        );
+        assert_eq!(section["probes_append"].as_array().unwrap().len(), 2);
+
+        let server = Facts {
+            host: "srv-db1".to_string(),
+            os: "freebsd".to_string(),
+            ..Facts::default()
+        };
+        let doc = resolved(&server);
+        let section = doc["Neofetch"].as_table().unwrap();
+        assert_eq!(section["separator"].as_str(), Some(":"));
+        assert_eq!(section["probes"].as_array().unwrap().len(), 1);
+        assert!(section.contains_key("probes_replace"));
+        assert_eq!(section["probes_append"].as_array().unwrap().len(), 1);
+
+        // Nothing to match: the system is never read.
+        let mut doc: toml::Table =
+            toml::from_str("[Neofetch]\nprobes = [{ OS = \"OS\" }]\n").unwrap();
+        resolve(&mut doc, || unreachable!("no condition to match")).unwrap();
+    }
+}
@@ -0,0 +1,562 @@
+//! `purr generate --from-neofetch`: translate a neofetch `config.conf`.
+//!
+//! A neofetch config is a bash script; it is read, not run. The `print_info()`
+//! body becomes the probe list (`info "Label" field` → the matching probe,
+//! `prin` → a fixed or command-backed line) and top-level `name=value`
+//! assignments become options. Anything without a purr equivalent (custom
+//! functions, conditionals, unsupported options) is skipped with a warning.
+
+use serde::de::{
+    DeserializeOwned, IntoDeserializer,
+    value::{Error as ValueError, StringDeserializer},
+};
+
+use super::{
+    Backend, BatteryOptions, CommandOptions, CpuOptions, CpuUsageOptions, DeOptions, DiskOptions,
+    DistroOptions, GpuOptions, KernelOptions, LabeledOptions, MemoryOptions,
+    NeofetchRendererConfig, PackagesOptions, ProbeConfig, ResolutionOptions, ShellOptions,
+    SongOptions, UptimeOptions,
+};
+
+/// neofetch's stock `print_info()`, used when a config does not define one.
+const DEFAULT_PRINT_INFO: [(&str, &str); 15] = [
+    ("OS", "distro"),
+    ("Host", "model"),
+    ("Kernel", "kernel"),
+    ("Uptime", "uptime"),
+    ("Packages", "packages"),
+    ("Shell", "shell"),
+    ("Resolution", "resolution"),
+    ("DE", "de"),
+    ("WM", "wm"),
+    ("WM Theme", "wm_theme"),
+    ("Theme", "theme"),
+    ("Icons", "icons"),
+    ("Terminal", "term"),
+    ("Terminal Font", "term_font"),
+    ("CPU", "cpu"),
+];
+
+/// The result of an import: the translated config plus everything that could
+/// not be carried over, as `line N: …` messages.
+#[derive(Debug)]
+pub struct Imported {
+    pub config: NeofetchRendererConfig,
+    pub warnings: Vec<String>,
+}
+
+/// A shell value: `name=value` or `name=(a b c)`.
+enum Value {
+    Scalar(String),
+    Array(Vec<String>),
+}
+
+impl Value {
+    fn scalar(&self) -> &str {
+        match self {
+            Value::Scalar(s) => s,
+            Value::Array(items) => items.first().map_or("", String::as_str),
+        }
+    }
+
+    fn items(&self) -> Vec<String> {
+        match self {
+            Value::Scalar(s) => s.split_whitespace().map(str::to_string).collect(),
+            Value::Array(items) => items.clone(),
+        }
+    }
+}
+
+/// Per-probe options set by assignments, applied to every probe of that kind.
+#[derive(Default)]
+struct Templates {
+    distro: DistroOptions,
+    kernel: KernelOptions,
+    uptime: UptimeOptions,
+    packages: PackagesOptions,
+    shell: ShellOptions,
+    resolution: ResolutionOptions,
+    de: DeOptions,
+    cpu: CpuOptions,
+    cpu_usage: CpuUsageOptions,
+    gpu: GpuOptions,
+    memory: MemoryOptions,
+    disk: DiskOptions,
+    battery: BatteryOptions,
+    song: SongOptions,
+}
+
+/// Translate the text of a neofetch config.
+pub fn from_neofetch(src: &str) -> Imported {
+    let mut warnings = Vec::new();
+    let mut print_info: Option<Vec<(usize, &str)>> = None;
+    let mut assignments = Vec::new();
+
+    let mut lines = src.lines().enumerate().map(|(i, l)| (i + 1, l.trim()));
+    while let Some((n, line)) = lines.next() {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line.starts_with("print_info()") {
+            let body = print_info.insert(Vec::new());
+            for (n, line) in lines.by_ref() {
+                match line {
+                    "}" => break,
+                    "{" => {}
+                    _ => body.push((n, line)),
+                }
+            }
+        } else if let Some((name, value)) = assignment(line) {
+            assignments.push((n, name, value));
+        } else {
+            warnings.push(format!(
+                "line {n}: unsupported shell construct `{line}`, skipped"
+            ));
+        }
+    }
+
+    let mut config = NeofetchRendererConfig::default();
+    let mut templates = Templates::default();
+    let mut underline_enabled = true;
+    let mut unsupported = Vec::new();
+    for (n, name, value) in &assignments {
+        let mut warn = |msg: String| warnings.push(format!("line {n}: {msg}"));
+        let s = value.scalar();
+        let on_off = |warn: &mut dyn FnMut(String)| match s {
+            "on" => Some(true),
+            "off" => Some(false),
+            _ => {
+                warn(format!("`{name}` should be `on` or `off`, found `{s}`"));
+                None
+            }
+        };
+        macro_rules! set {
+            ($slot:expr, $parsed:expr) => {
+                if let Some(v) = $parsed {
+                    $slot = v;
+                }
+            };
+        }
+        macro_rules! choice {
+            () => {
+                parse_enum(s).or_else(|| {
+                    warn(format!("unsupported value `{s}` for `{name}`"));
+                    None
+                })
+            };
+        }
+        match name.as_str() {
+            // Text and title.
+            "title_fqdn" => set!(config.title_fqdn, on_off(&mut warn)),
+            "separator" => config.separator = s.to_string(),
+            "bold" => set!(config.bold, on_off(&mut warn)),
+            "underline_enabled" => set!(underline_enabled, on_off(&mut warn)),
+            "underline_char" => config.underline_char = s.to_string(),
+            "colors" => config.colors = colour_list(value),
+            // Logo.
+            "ascii_distro" => {
+                config.ascii.distro = (s != "auto" && !s.is_empty()).then(|| s.to_string())
+            }
+            "ascii_colors" => config.ascii.colors = colour_list(value),
+            "ascii_bold" => set!(config.ascii.bold, on_off(&mut warn)),
+            "image_backend" => match s {
+                "ascii" => config.backend = Backend::Ascii,
+                "kitty" => config.backend = Backend::Kitty,
+                "iterm2" => config.backend = Backend::Iterm2,
+                "sixel" => config.backend = Backend::Sixel,
+                "blocks" => config.backend = Backend::Blocks,
+                "off" => config.backend = Backend::Off,
+                _ => warn(format!("image backend `{s}` is not supported, using ascii")),
+            },
+            "image_source" => match s {
+                "auto" | "ascii" | "" => {}
+                path => config.image_source = Some(path.into()),
+            },
+            // Colour blocks.
+            "block_range" => match value.items()[..] {
+                [ref start, ref end] => match (start.parse(), end.parse()) {
+                    (Ok(start), Ok(end)) => config.color_blocks.range = [start, end],
+                    _ => warn(format!("`block_range` should be two colours, found `{s}`")),
+                },
+                _ => warn("`block_range` should hold a start and an end".to_string()),
+            },
+            "block_width" => set!(config.color_blocks.width, number(s, &mut warn)),
+            "block_height" => set!(config.color_blocks.height, number(s, &mut warn)),
+            "col_offset" => {
+                config.color_blocks.offset = match s {
+                    "auto" => None,
+                    _ => number(s, &mut warn),
+                }
+            }
+            // Usage bars.
+            "bar_length" => set!(config.bars.length, number(s, &mut warn)),
+            "bar_char_elapsed" => config.bars.char_elapsed = s.to_string(),
+            "bar_char_total" => config.bars.char_total = s.to_string(),
+            "bar_border" => set!(config.bars.border, on_off(&mut warn)),
+            "bar_color_elapsed" => {
+                config.bars.color_elapsed = match s {
+                    "distro" => None,
+                    _ => number(s, &mut warn),
+                }
+            }
+            "bar_color_total" => {
+                config.bars.color_total = match s {
+                    "distro" => None,
+                    _ => number(s, &mut warn),
+                }
+            }
+            "cpu_display" => set!(templates.cpu_usage.bar, choice!()),
+            "memory_display" => set!(templates.memory.bar, choice!()),
+            "battery_display" => set!(templates.battery.bar, choice!()),
+            "disk_display" => set!(templates.disk.bar, choice!()),
+            // Per-probe options.
+            "distro_shorthand" => set!(templates.distro.shorthand, choice!()),
+            "os_arch" => set!(templates.distro.os_arch, on_off(&mut warn)),
+            "kernel_shorthand" => set!(templates.kernel.shorthand, on_off(&mut warn)),
+            "uptime_shorthand" => set!(templates.uptime.format, choice!()),
+            "package_managers" => set!(templates.packages.display, choice!()),
+            "shell_path" => set!(templates.shell.path, on_off(&mut warn)),
+            "shell_version" => set!(templates.shell.version, on_off(&mut warn)),
+            "refresh_rate" => set!(templates.resolution.refresh_rate, on_off(&mut warn)),
+            "de_version" => set!(templates.de.version, on_off(&mut warn)),
+            "cpu_brand" => set!(templates.cpu.brand, on_off(&mut warn)),
+            "cpu_cores" => set!(templates.cpu.cores, choice!()),
+            "cpu_speed" => set!(templates.cpu.speed, on_off(&mut warn)),
+            "speed_type" => set!(templates.cpu.speed_type, choice!()),
+            "speed_shorthand" => set!(templates.cpu.speed_shorthand, on_off(&mut warn)),
+            "cpu_temp" => match s {
+                "off" => templates.cpu.temp = None,
+                _ => set!(templates.cpu.temp, choice!().map(Some)),
+            },
+            "gpu_brand" => set!(templates.gpu.brand, on_off(&mut warn)),
+            "gpu_type" => set!(templates.gpu.gpu_type, choice!()),
+            "memory_unit" => set!(templates.memory.unit, choice!()),
+            "memory_percent" => set!(templates.memory.percent, on_off(&mut warn)),
+            "disk_show" => templates.disk.show = value.items(),
+            "disk_subtitle" => set!(templates.disk.subtitle, choice!()),
+            "disk_percent" => set!(templates.disk.percent, on_off(&mut warn)),
+            "music_player" => templates.song.player = s.to_string(),
+            "song_format" => templates.song.format = s.to_string(),
+            "song_shorthand" => set!(templates.song.shorthand, on_off(&mut warn)),
+            _ => unsupported.push(format!("{name} (line {n})")),
+        }
+    }
+    if !unsupported.is_empty() {
+        warnings.push(format!(
+            "options without a purr equivalent, ignored: {}",
+            unsupported.join(", ")
+        ));
+    }
+
+    let body = match print_info {
+        Some(body) => {
+            config.title = false;
+            config.underline = false;
+            config.col = false;
+            body
+        }
+        None => {
+            warnings.push("no print_info() found, using neofetch's default info lines".to_string());
+            config.probes = DEFAULT_PRINT_INFO
+                .iter()
+                .filter_map(|(label, field)| probe(field, label, &templates))
+                .collect();
+            config.underline &= underline_enabled;
+            return Imported { config, warnings };
+        }
+    };
+    config.probes.clear();
+    for (n, line) in body {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let args = words(line);
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        match args[..] {
+            ["info", "title"] => config.title = true,
+            ["info", "underline"] => config.underline = true,
+            ["info", "cols"] => config.col = true,
+            ["info", "line_break"] | ["prin"] => config.probes.push(ProbeConfig::Blank),
+            ["info", field] | ["info", _, field] if probe(field, "", &templates).is_none() => {
+                warnings.push(format!(
+                    "line {n}: unsupported info field `{field}`, skipped"
+                ));
+            }
+            ["info", field] => config.probes.extend(probe(field, "", &templates)),
+            ["info", label, field] => config.probes.extend(probe(field, label, &templates)),
+            ["prin", text] => config.probes.push(prin("", text)),
+            ["prin", label, text] => config.probes.push(prin(label, text)),
+            _ => warnings.push(format!(
+                "line {n}: unsupported print_info line `{line}`, skipped"
+            )),
+        }
+    }
+    config.underline &= underline_enabled;
+    Imported { config, warnings }
+}
+
+/// The probe for a neofetch `info` field, carrying the options set for it.
+fn probe(field: &str, label: &str, t: &Templates) -> Option<ProbeConfig> {
+    let label = label.to_string();
+    let plain = || LabeledOptions::from(label.clone());
+    Some(match field {
+        "distro" => ProbeConfig::OS(DistroOptions {
+            label,
+            ..t.distro.clone()
+        }),
+        "model" => ProbeConfig::Model(plain()),
+        "kernel" => ProbeConfig::Kernel(KernelOptions {
+            label,
+            ..t.kernel.clone()
+        }),
+        "uptime" => ProbeConfig::Uptime(UptimeOptions {
+            label,
+            ..t.uptime.clone()
+        }),
+        "packages" => ProbeConfig::Packages(PackagesOptions {
+            label,
+            ..t.packages.clone()
+        }),
+        "shell" => ProbeConfig::Shell(ShellOptions {
+            label,
+            ..t.shell.clone()
+        }),
+        "editor" => ProbeConfig::Editor(plain()),
+        "resolution" => ProbeConfig::Resolution(ResolutionOptions {
+            label,
+            ..t.resolution.clone()
+        }),
+        "de" => ProbeConfig::DE(DeOptions {
+            label,
+            ..t.de.clone()
+        }),
+        "wm" => ProbeConfig::WM(plain()),
+        "wm_theme" => ProbeConfig::WMTheme(plain()),
+        "theme" => ProbeConfig::Theme(plain()),
+        "icons" => ProbeConfig::Icons(plain()),
+        "cursor" => ProbeConfig::Cursor(plain()),
+        "term" => ProbeConfig::Terminal(plain()),
+        "term_font" => ProbeConfig::TerminalFont(plain()),
+        "cpu" => ProbeConfig::CPU(CpuOptions {
+            label,
+            ..t.cpu.clone()
+        }),
+        "gpu" => ProbeConfig::GPU(GpuOptions {
+            label,
+            ..t.gpu.clone()
+        }),
+        "gpu_driver" => ProbeConfig::GPUDriver(plain()),
+        "cpu_usage" => ProbeConfig::CPUUsage(CpuUsageOptions {
+            label,
+            ..t.cpu_usage.clone()
+        }),
+        "memory" => ProbeConfig::Memory(MemoryOptions {
+            label,
+            ..t.memory.clone()
+        }),
+        "disk" => ProbeConfig::Disk(DiskOptions {
+            label,
+            ..t.disk.clone()
+        }),
+        "battery" => ProbeConfig::Battery(BatteryOptions {
+            label,
+            ..t.battery.clone()
+        }),
+        "font" => ProbeConfig::Font(plain()),
+        "song" => ProbeConfig::Song(SongOptions {
+            label,
+            ..t.song.clone()
+        }),
+        "local_ip" => ProbeConfig::LocalIP(plain()),
+        "public_ip" => ProbeConfig::PublicIP(plain()),
+        "users" => ProbeConfig::Users(plain()),
+        "locale" => ProbeConfig::Locale(plain()),
+        _ => return None,
+    })
+}
+
+/// A `prin` line: fixed text, or a shell-evaluated line when it expands
+/// anything (`$(...)`, `$VAR`, backticks).
+fn prin(label: &str, text: &str) -> ProbeConfig {
+    if text.contains(['$', '`']) {
+        let quoted = text.replace('\\', "\\\\").replace('"', "\\\"");
+        ProbeConfig::Command(CommandOptions {
+            label: label.to_string(),
+            command: format!("printf '%s\\n' \"{quoted}\""),
+            shell: true,
+            ..Default::default()
+        })
+    } else {
+        ProbeConfig::Text {
+            label: label.to_string(),
+            value: text.to_string(),
+        }
+    }
+}
+
+/// Split `name=value` / `name=(a b c)`.
+fn assignment(line: &str) -> Option<(String, Value)> {
+    let (name, rest) = line.split_once('=')?;
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return None;
+    }
+    let value = match rest.strip_prefix('(') {
+        Some(inner) => Value::Array(words(inner.rsplit_once(')').map_or(inner, |(i, _)| i))),
+        None => Value::Scalar(words(rest).into_iter().next().unwrap_or_default()),
+    };
+    Some((name.to_string(), value))
+}
+
+/// Split a shell line into words, honouring quotes and stopping at a comment.
+fn words(line: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                let w = word.get_or_insert_default();
+                w.extend(chars.by_ref().take_while(|&c| c != '\''));
+            }
+            '"' => {
+                let w = word.get_or_insert_default();
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' => w.extend(chars.next()),
+                        c => w.push(c),
+                    }
+                }
+            }
+            '#' if word.is_none() => break,
+            c if c.is_whitespace() => words.extend(word.take()),
+            c => word.get_or_insert_default().push(c),
+        }
+    }
+    words.extend(word);
+    words
+}
+
+/// A neofetch colour list; `distro` (the default) means the logo's colours.
+fn colour_list(value: &Value) -> Vec<u8> {
+    let items = value.items();
+    if items.first().is_some_and(|i| i == "distro") {
+        return Vec::new();
+    }
+    items.iter().filter_map(|i| i.parse().ok()).collect()
+}
+
+fn number<T: std::str::FromStr>(s: &str, warn: &mut dyn FnMut(String)) -> Option<T> {
+    let n = s.parse().ok();
+    if n.is_none() {
+        warn(format!("expected a number, found `{s}`"));
+    }
+    n
+}
+
+/// Parse a neofetch option value with the serde spelling of the purr option
+/// (neofetch writes `C`/`F`, purr `c`/`f`).
+fn parse_enum<T: DeserializeOwned>(s: &str) -> Option<T> {
+    let de: StringDeserializer<ValueError> = s.to_lowercase().into_deserializer();
+    T::deserialize(de).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{CoresMode, MemoryUnit, TempUnit};
+
+    const CONF: &str = r#"
+# See this wiki page for more info:
+print_info() {
+    info title
+    info underline
+
+    info "OS" distro
+    info "Mem" memory
+    info kernel
+    prin "Date" "$(date +%F)"
+    prin "Team" "platform"
+    info "Weather" weather
+    [[ $player ]] && prin "Music Player" "$player"
+    info line_break
+    info cols
+}
+
+memory_unit="gib"
+memory_percent="maybe"
+cpu_cores="physical"   # logical, physical or off
+cpu_temp="F"
+disk_show=('/' '/home')
+colors=(4 6 1 8 8 6)
+ascii_distro="arch"
+block_range=(1 8)
+col_offset="auto"
+underline_char="="
+gtk_shorthand="off"
+"#;
+
+    #[test]
+    fn imports_print_info_and_options() {
+        let Imported { config, warnings } = from_neofetch(CONF);
+        assert!(config.title && config.underline && config.col);
+        assert_eq!(config.underline_char, "=");
+        assert_eq!(config.colors, [4, 6, 1, 8, 8, 6]);
+        assert_eq!(config.ascii.distro.as_deref(), Some("arch"));
+        assert_eq!(config.color_blocks.range, [1, 8]);
+
+        let ids: Vec<_> = config.probes.iter().map(|p| (p.id(), p.label())).collect();
+        assert_eq!(
+            ids,
+            [
+                ("os", "OS"),
+                ("memory", "Mem"),
+                ("kernel", ""),
+                ("command", "Date"),
+                ("text", "Team"),
+                ("blank", ""),
+            ]
+        );
+        match &config.probes[1] {
+            ProbeConfig::Memory(o) => {
+                assert_eq!(o.unit, MemoryUnit::Gib);
+                assert!(!o.percent);
+            }
+            other => panic!("expected memory, got {other:?}"),
+        }
+        match &config.probes[3] {
+            ProbeConfig::Command(o) => {
+                assert_eq!(o.command, r#"printf '%s\n' "$(date +%F)""#);
+                assert!(o.shell);
+            }
+            other => panic!("expected command, got {other:?}"),
+        }
+
+        assert_eq!(
+            warnings,
+            [
+                "line 19: `memory_percent` should be `on` or `off`, found `maybe`",
+                "options without a purr equivalent, ignored: gtk_shorthand (line 28)",
+                "line 12: unsupported info field `weather`, skipped",
+                "line 13: unsupported print_info line `[[ $player ]] && prin \"Music Player\" \"$player\"`, skipped",
+            ]
+        );
+    }
+
+    #[test]
+    fn options_apply_without_print_info() {
+        let Imported { config, warnings } = from_neofetch("cpu_cores=\"off\"\ncpu_temp=\"C\"\n");
+        assert_eq!(
+            warnings,
+            ["no print_info() found, using neofetch's default info lines"]
+        );
+        assert_eq!(config.probes.len(), DEFAULT_PRINT_INFO.len());
+        let cpu = config.probes.iter().find_map(|p| match p {
+            ProbeConfig::CPU(o) => Some(o),
+            _ => None,
+        });
+        let cpu = cpu.expect("default info lines include the CPU");
+        assert_eq!(cpu.cores, CoresMode::Off);
+        assert_eq!(cpu.temp, Some(TempUnit::C));
+    }
+}
@@ -0,0 +1,216 @@
+//! Config introspection: the shape of every option a config file may set.
+//!
+//! Each config struct describes itself through [`Describe`], built from the
+//! same field list (and `///` docs) as the struct definition, so tooling that
+//! walks a config file (`purr config check`) cannot drift from what the
+//! loader actually accepts. Enum values come straight from serde.
+
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+
+/// The shape of a config value.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Kind {
+    Bool,
+    /// An integer within `min..=max`.
+    Int {
+        min: i64,
+        max: i64,
+    },
+    Str,
+    Path,
+    /// One of a fixed set of strings.
+    Enum(&'static [&'static str]),
+    /// An array of any length.
+    List(Box<Kind>),
+    /// An array of exactly `len` elements.
+    Array(usize, Box<Kind>),
+    /// May be left out.
+    Optional(Box<Kind>),
+    /// A table with these keys.
+    Table(Vec<Field>),
+    /// A table with keys of the user's choosing, each holding this kind.
+    Map(Box<Kind>),
+    /// A probe's options: a bare label string or a table with these keys.
+    Options(Vec<Field>),
+    /// One entry of a probe list; see [`crate::config::ProbeConfig::variant_kinds`].
+    Probe,
+    /// A variant without a value, written as its bare name (`"Blank"`).
+    Unit,
+}
+
+/// One key of a config table.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Field {
+    pub name: &'static str,
+    /// Raw `///` doc text, one source line per line.
+    pub doc: &'static str,
+    pub kind: Kind,
+    /// Must be present (no serde default to fall back on).
+    pub required: bool,
+}
+
+impl Field {
+    pub fn new(name: &'static str, doc: &'static str, kind: Kind) -> Self {
+        Self {
+            name,
+            doc,
+            kind,
+            required: false,
+        }
+    }
+
+    /// Mark the field as one that has to be given.
+    pub fn required(self) -> Self {
+        Self {
+            required: true,
+            ..self
+        }
+    }
+
+    /// The doc comment as trimmed lines, without leading/trailing blanks.
+    pub fn doc_lines(&self) -> Vec<&'static str> {
+        let lines: Vec<_> = self.doc.lines().map(str::trim).collect();
+        let start = lines
+            .iter()
+            .position(|l| !l.is_empty())
+            .unwrap_or(lines.len());
+        let end = lines
+            .iter()
+            .rposition(|l| !l.is_empty())
+            .map_or(start, |i| i + 1);
+        lines[start..end].to_vec()
+    }
+}
+
+/// A type that can describe the config values it deserializes from.
+pub trait Describe {
+    fn kind() -> Kind;
+}
+
+impl Describe for bool {
+    fn kind() -> Kind {
+        Kind::Bool
+    }
+}
+
+macro_rules! describe_int {
+    ($($ty:ty),*) => {$(
+        impl Describe for $ty {
+            fn kind() -> Kind {
+                // TOML integers are i64; wider types are capped there.
+                Kind::Int { min: <$ty>::MIN as i64, max: i64::try_from(<$ty>::MAX).unwrap_or(i64::MAX) }
+            }
+        }
+    )*};
+}
+describe_int!(u8, u16, u32, u64);
+
+impl Describe for String {
+    fn kind() -> Kind {
+        Kind::Str
+    }
+}
+
+impl Describe for std::path::PathBuf {
+    fn kind() -> Kind {
+        Kind::Path
+    }
+}
+
+impl<T: Describe> Describe for Vec<T> {
+    fn kind() -> Kind {
+        Kind::List(Box::new(T::kind()))
+    }
+}
+
+impl<T: Describe, const N: usize> Describe for [T; N] {
+    fn kind() -> Kind {
+        Kind::Array(N, Box::new(T::kind()))
+    }
+}
+
+impl<T: Describe> Describe for Option<T> {
+    fn kind() -> Kind {
+        Kind::Optional(Box::new(T::kind()))
+    }
+}
+
+/// The variant (or field) names serde's derived `Deserialize` for `T` asks
+/// for, i.e. exactly the spellings a config file must use.
+pub fn serde_names<T: for<'de> Deserialize<'de>>() -> &'static [&'static str] {
+    let mut names = Introspect(&[]);
+    let _ = T::deserialize(&mut names);
+    names.0
+}
+
+/// A deserializer that records the names a derived impl hands to
+/// `deserialize_enum`/`deserialize_struct`, then bails out.
+struct Introspect(&'static [&'static str]);
+
+impl<'de> Deserializer<'de> for &mut Introspect {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _: V) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom("introspection only"))
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        variants: &'static [&'static str],
+        _: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.0 = variants;
+        Err(de::Error::custom("introspection only"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        fields: &'static [&'static str],
+        _: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.0 = fields;
+        Err(de::Error::custom("introspection only"))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{
+        ColorBlocks, JsonRendererConfig, MemoryUnit, NeofetchRendererConfig, ProbeConfig,
+    };
+
+    fn names(kind: Kind) -> Vec<&'static str> {
+        match kind {
+            Kind::Table(fields) => fields.iter().map(|f| f.name).collect(),
+            other => panic!("not a table: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn descriptions_match_serde() {
+        assert_eq!(serde_names::<MemoryUnit>(), ["kib", "mib", "gib"]);
+        assert_eq!(
+            names(NeofetchRendererConfig::kind()),
+            serde_names::<NeofetchRendererConfig>()
+        );
+        assert_eq!(
+            names(JsonRendererConfig::kind()),
+            serde_names::<JsonRendererConfig>()
+        );
+        assert_eq!(names(ColorBlocks::kind()), serde_names::<ColorBlocks>());
+        let variants: Vec<_> = ProbeConfig::variant_kinds()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(variants, serde_names::<ProbeConfig>());
+    }
+}
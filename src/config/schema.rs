@@ -0,0 +1,184 @@
+//! `purr config schema`: a JSON Schema for the config file.
+//!
+//! Generated from the same [`Kind`] descriptions `purr config check` walks, so
+//! editors validating against it (e.g. taplo) accept exactly what the loader
+//! does. Probe entries are a shared `probe` definition: one alternative per
+//! variant, each taking a bare label string or its options table.
+
+use serde_json::{Map, Value, json};
+
+use super::{
+    Config, ProbeConfig,
+    meta::{Field, Kind},
+};
+
+/// The JSON Schema for a config file, as draft-07 since that is the draft
+/// editor tooling (taplo, VS Code) supports best.
+pub fn config_schema() -> Value {
+    let mut schema = schema(&Config::layer_kind());
+    // Without an `extends` to build on, a file has to be complete.
+    let Kind::Table(fields) = Config::document_kind() else {
+        unreachable!("a config document describes as a table");
+    };
+    let sections: Map<String, Value> = fields
+        .iter()
+        .filter_map(|f| match &f.kind {
+            Kind::Optional(inner) => match inner.as_ref() {
+                Kind::Table(section) => Some((f.name.to_string(), required(section))),
+                _ => None,
+            },
+            _ => None,
+        })
+        .filter(|(_, required)| !required.is_empty())
+        .map(|(name, required)| (name, json!({ "required": required })))
+        .collect();
+    schema["if"] = json!({ "not": { "required": ["extends"] } });
+    schema["then"] = json!({ "properties": sections });
+    let probes: Vec<Value> = ProbeConfig::variant_kinds()
+        .iter()
+        .map(|(name, kind)| variant(name, kind))
+        .collect();
+    schema["$schema"] = json!("http://json-schema.org/draft-07/schema#");
+    schema["title"] = json!("purr config");
+    schema["definitions"] = json!({
+        "probe": { "description": "One info line.", "oneOf": probes },
+    });
+    schema
+}
+
+/// One probe variant: `{ Name = <value> }`, or the bare `"Name"` for unit
+/// variants (`Blank`).
+fn variant(name: &str, kind: &Kind) -> Value {
+    match kind {
+        Kind::Unit => json!({
+            "anyOf": [
+                { "const": name },
+                object([(name.to_string(), json!({ "type": "object", "maxProperties": 0 }))], &[name]),
+            ]
+        }),
+        kind => object([(name.to_string(), schema(kind))], &[name]),
+    }
+}
+
+fn schema(kind: &Kind) -> Value {
+    match kind {
+        Kind::Bool => json!({ "type": "boolean" }),
+        Kind::Int { min, max } => json!({ "type": "integer", "minimum": min, "maximum": max }),
+        Kind::Str | Kind::Path => json!({ "type": "string" }),
+        Kind::Enum(names) => json!({ "type": "string", "enum": names }),
+        Kind::List(item) => json!({ "type": "array", "items": schema(item) }),
+        Kind::Array(len, item) => json!({
+            "type": "array",
+            "items": schema(item),
+            "minItems": len,
+            "maxItems": len,
+        }),
+        Kind::Optional(inner) => schema(inner),
+        Kind::Table(fields) => table(fields),
+        Kind::Map(item) => json!({ "type": "object", "additionalProperties": schema(item) }),
+        Kind::Options(fields) => json!({
+            "anyOf": [
+                { "type": "string", "description": "The probe's label, with default options." },
+                table(fields),
+            ]
+        }),
+        Kind::Probe => json!({ "$ref": "#/definitions/probe" }),
+        Kind::Unit => json!({ "type": "object", "maxProperties": 0 }),
+    }
+}
+
+fn table(fields: &[Field]) -> Value {
+    let properties = fields.iter().map(|f| {
+        let mut property = schema(&f.kind);
+        let doc = f.doc_lines().join(" ");
+        if !doc.is_empty() {
+            property["description"] = json!(doc);
+        }
+        (f.name.to_string(), property)
+    });
+    object(properties, &required(fields))
+}
+
+fn required(fields: &[Field]) -> Vec<&'static str> {
+    fields
+        .iter()
+        .filter(|f| f.required)
+        .map(|f| f.name)
+        .collect()
+}
+
+/// A closed object schema: only `properties` may appear.
+fn object(properties: impl IntoIterator<Item = (String, Value)>, required: &[&str]) -> Value {
+    let mut schema = json!({
+        "type": "object",
+        "properties": properties.into_iter().collect::<Map<_, _>>(),
+        "additionalProperties": false,
+    });
+    if !required.is_empty() {
+        schema["required"] = json!(required);
+    }
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn probes_accept_a_label_or_an_options_table() {
+        let schema = config_schema();
+        assert_eq!(
+            schema["properties"]["Neofetch"]["properties"]["probes"]["items"],
+            json!({ "$ref": "#/definitions/probe" })
+        );
+        let probes = schema["definitions"]["probe"]["oneOf"].as_array().unwrap();
+        let memory = probes
+            .iter()
+            .find_map(|p| p["properties"].get("Memory"))
+            .expect("a Memory alternative");
+        assert_eq!(memory["anyOf"][0]["type"], "string");
+        let options = &memory["anyOf"][1];
+        assert_eq!(options["additionalProperties"], false);
+        assert_eq!(
+            options["properties"]["unit"],
+            json!({
+                "type": "string",
+                "enum": ["kib", "mib", "gib"],
+                "description": "Unit for used/total memory.",
+            })
+        );
+        assert!(
+            probes
+                .iter()
+                .any(|p| p["anyOf"][0] == json!({ "const": "Blank" }))
+        );
+        let header = probes
+            .iter()
+            .find_map(|p| p["properties"].get("Header"))
+            .unwrap();
+        assert_eq!(header["required"], json!(["text"]));
+        assert_eq!(
+            schema["properties"]["Neofetch"]["properties"]["color_blocks"]["properties"]["range"],
+            json!({
+                "type": "array",
+                "items": { "type": "integer", "minimum": 0, "maximum": 255 },
+                "minItems": 2,
+                "maxItems": 2,
+                "description": "Inclusive `[start, end]` palette range to display.",
+            })
+        );
+    }
+
+    #[test]
+    fn complete_files_need_every_key_without_a_default() {
+        let schema = config_schema();
+        assert_eq!(schema["if"], json!({ "not": { "required": ["extends"] } }));
+        assert_eq!(
+            schema["then"]["properties"]["Neofetch"]["required"],
+            json!(["title", "underline", "col"])
+        );
+        // Json's keys all have defaults; a file that `extends` needs none.
+        assert!(schema["then"]["properties"].get("Json").is_none());
+        assert!(schema["properties"]["Neofetch"].get("required").is_none());
+    }
+}
@@ -0,0 +1,247 @@
+//! `purr config show`: the effective config once every layer (defaults,
+//! config files, command-line flags) is applied, optionally noting which
+//! layer set each value.
+
+use std::{collections::BTreeMap, fmt, fmt::Write as _, path::PathBuf};
+
+use toml::Value;
+
+use super::{Config, annotate::write_probe_entry};
+
+/// A layer of the effective config.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Origin {
+    /// The renderer's built-in defaults.
+    Default,
+    File(PathBuf),
+    CommandLine,
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Origin::Default => f.write_str("default"),
+            Origin::File(path) => write!(f, "{}", path.display()),
+            Origin::CommandLine => f.write_str("command line"),
+        }
+    }
+}
+
+/// Which layer last changed each value, keyed by path (`Neofetch.separator`,
+/// `Neofetch.probes[3]`). `stages` is the config after each layer in order,
+/// the last being the effective one. Probe entries are credited as a whole,
+/// to the layer that added them (or last changed them), since list edits
+/// shift their positions.
+pub fn origins(stages: &[(Origin, Config)]) -> Result<BTreeMap<String, Origin>, toml::ser::Error> {
+    let Some((_, last)) = stages.last() else {
+        return Ok(BTreeMap::new());
+    };
+    let target = last.renderer();
+    let values = stages
+        .iter()
+        .map(|(_, config)| Value::try_from(config.clone().with_renderer(target)))
+        .collect::<Result<Vec<_>, _>>()?;
+    let layers: Vec<&Origin> = stages.iter().map(|(origin, _)| origin).collect();
+    let mut out = BTreeMap::new();
+    walk(
+        &mut out,
+        String::new(),
+        &layers,
+        values.iter().map(Some).collect(),
+    );
+    Ok(out)
+}
+
+/// Credit the value at `path`; `at` holds it in each stage (if present).
+fn walk(
+    out: &mut BTreeMap<String, Origin>,
+    path: String,
+    layers: &[&Origin],
+    at: Vec<Option<&Value>>,
+) {
+    let credit = |changed: &dyn Fn(Option<&Value>, Option<&Value>) -> bool| {
+        let k = (1..at.len())
+            .rev()
+            .find(|&k| changed(at[k - 1], at[k]))
+            .unwrap_or(0);
+        layers[k].clone()
+    };
+    match at.last().copied().flatten() {
+        Some(Value::Table(table)) => {
+            for key in table.keys() {
+                let child = at.iter().map(|v| v.and_then(|v| v.get(key))).collect();
+                walk(out, join(&path, key), layers, child);
+            }
+        }
+        Some(Value::Array(items)) if items.iter().any(Value::is_table) => {
+            for (i, item) in items.iter().enumerate() {
+                let has = |v: Option<&Value>| {
+                    v.and_then(Value::as_array)
+                        .is_some_and(|list| list.contains(item))
+                };
+                let origin = credit(&|before, now| !has(before) && has(now));
+                out.insert(format!("{path}[{i}]"), origin);
+            }
+        }
+        Some(_) => {
+            let origin = credit(&|before, now| before != now);
+            out.insert(path, origin);
+        }
+        None => {}
+    }
+}
+
+/// The config as TOML, with a `# <layer>` comment on each value when
+/// `origins` is given.
+pub fn to_toml(
+    config: &Config,
+    origins: Option<&BTreeMap<String, Origin>>,
+) -> Result<String, toml::ser::Error> {
+    let Value::Table(doc) = Value::try_from(config)? else {
+        unreachable!("a config serializes to a table");
+    };
+    let mut out = String::new();
+    for (key, value) in &doc {
+        if let Value::Table(section) = value {
+            write_table(&mut out, key, section, origins);
+        }
+    }
+    Ok(out)
+}
+
+/// A `[path]` table: plain values, then sub-tables, then probe lists as
+/// `[[path.probes]]` entries.
+fn write_table(
+    out: &mut String,
+    path: &str,
+    table: &toml::Table,
+    origins: Option<&BTreeMap<String, Origin>>,
+) {
+    let _ = writeln!(out, "[{path}]");
+    write_values(out, path, table, origins);
+    for (key, value) in table {
+        if let Value::Table(sub) = value {
+            out.push('\n');
+            write_table(out, &join(path, key), sub, origins);
+        }
+    }
+    for (key, value) in table {
+        if let Value::Array(items) = value
+            && items.iter().any(Value::is_table)
+        {
+            let list = join(path, key);
+            for (i, item) in items.iter().enumerate() {
+                out.push('\n');
+                let _ = write!(out, "[[{list}]]");
+                note(out, origins, &format!("{list}[{i}]"));
+                write_probe_entry(out, &list, item, |out, _, body| {
+                    write_values(out, &list, body, None);
+                });
+            }
+        }
+    }
+}
+
+/// The `key = value` lines of a table (everything but sub-tables and probe
+/// lists).
+fn write_values(
+    out: &mut String,
+    path: &str,
+    table: &toml::Table,
+    origins: Option<&BTreeMap<String, Origin>>,
+) {
+    for (key, value) in table {
+        let nested = match value {
+            Value::Table(_) => true,
+            Value::Array(items) => items.iter().any(Value::is_table),
+            _ => false,
+        };
+        if !nested {
+            let _ = write!(out, "{key} = {value}");
+            note(out, origins, &join(path, key));
+        }
+    }
+}
+
+/// End a line with the layer that set `path`, if origins are shown.
+fn note(out: &mut String, origins: Option<&BTreeMap<String, Origin>>, path: &str) {
+    if let Some(origin) = origins.and_then(|o| o.get(path)) {
+        let _ = write!(out, "  # {origin}");
+    }
+    out.push('\n');
+}
+
+fn join(path: &str, key: &str) -> String {
+    match path {
+        "" => key.to_string(),
+        _ => format!("{path}.{key}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ProbeConfig, RendererOverride};
+
+    #[test]
+    fn values_are_credited_to_the_layer_that_set_them() {
+        let system = Origin::File(PathBuf::from("/etc/purr/config.toml"));
+        let Config::Neofetch(mut file) = Config::default() else {
+            unreachable!()
+        };
+        file.separator = " ->".to_string();
+        file.probes.push(toml::from_str("Disk = \"Disk\"").unwrap());
+        let mut flags = file.clone();
+        flags.bold = false;
+        flags.probes.retain(|p| p.id() != "gpu");
+        let stages = [
+            (Origin::Default, Config::default()),
+            (system.clone(), Config::Neofetch(file)),
+            (Origin::CommandLine, Config::Neofetch(flags)),
+        ];
+
+        let origins = origins(&stages).unwrap();
+        assert_eq!(origins["Neofetch.separator"], system);
+        assert_eq!(origins["Neofetch.bold"], Origin::CommandLine);
+        assert_eq!(origins["Neofetch.title"], Origin::Default);
+        let last = ProbeConfig::default_neofetch().len() - 1;
+        assert_eq!(origins[&format!("Neofetch.probes[{last}]")], system);
+        assert_eq!(origins["Neofetch.probes[0]"], Origin::Default);
+
+        let (_, effective) = &stages[2];
+        let shown = to_toml(effective, Some(&origins)).unwrap();
+        assert!(shown.contains("separator = \" ->\"  # /etc/purr/config.toml\n"));
+        assert!(
+            shown
+                .contains("[[Neofetch.probes]]  # /etc/purr/config.toml\n[Neofetch.probes.Disk]\n")
+        );
+        let reparsed: Config = toml::from_str(&shown).unwrap();
+        assert_eq!(
+            toml::to_string(&reparsed).unwrap(),
+            toml::to_string(effective).unwrap()
+        );
+        assert_eq!(reparsed.renderer(), RendererOverride::Neofetch);
+    }
+
+    #[test]
+    fn extends_in_a_higher_layer_keeps_lower_credits() {
+        let dir = std::env::temp_dir().join(format!("purr-origins-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let (user, env) = (dir.join("config.toml"), dir.join("env.toml"));
+        std::fs::write(&user, "[Neofetch]\nseparator = \" =>\"\n").unwrap();
+        std::fs::write(&env, "extends = \"neofetch\"\n[Neofetch]\nbold = false\n").unwrap();
+        let files = [user.clone(), env.clone()];
+        let mut stages = vec![(Origin::Default, Config::default())];
+        for i in 0..files.len() {
+            let config = Config::from_files(&files[..=i], None).unwrap();
+            stages.push((Origin::File(files[i].clone()), config));
+        }
+
+        let origins = origins(&stages).unwrap();
+        assert_eq!(origins["Neofetch.separator"], Origin::File(user));
+        assert_eq!(origins["Neofetch.bold"], Origin::File(env));
+        assert_eq!(origins["Neofetch.title"], Origin::Default);
+        assert_eq!(origins["Neofetch.probes[0]"], Origin::Default);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
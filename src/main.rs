@@ -2,12 +2,17 @@
 #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
 compile_error!("This crate is only supported on Linux, macOS, and Windows.");
 
+use std::path::PathBuf;
+
 use clap::Parser;
 use tracing::{Level, debug, info, info_span};
 
 use purr_lib::{
-    cli::{Cli, Command},
-    config::{Config, RendererOverride},
+    cli::{Cli, Command, ConfigCommand},
+    config::{
+        Config, ConfigParseError, RendererOverride, check, import, schema,
+        show::{self, Origin},
+    },
     renderer::{json::JsonRenderer, neofetch::NeofetchRenderer},
 };
 
@@ -68,7 +73,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 }
 
                 // Determine which preset to generate
-                let default_config = if args.neofetch {
+                let default_config = if let Some(path) = &args.from_neofetch {
+                    let imported = import::from_neofetch(&std::fs::read_to_string(path)?);
+                    for warning in &imported.warnings {
+                        eprintln!("warning: {warning}");
+                    }
+                    Config::Neofetch(imported.config)
+                } else if args.neofetch {
                     if args.all {
                         Config::default_neofetch_all()
                     } else {
@@ -84,7 +95,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 println!("Config file generated successfully");
                 return Ok(());
             }
-            Command::ConfigPath => {
+            Command::ConfigPath { all: true } => {
+                for path in Config::config_files() {
+                    println!("{}", path.display());
+                }
+                return Ok(());
+            }
+            Command::ConfigPath { all: false } => {
                 // Return default config file path
                 debug!("Returning default config file path");
                 let config_path = Config::get_config_dir()
@@ -93,6 +110,73 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 println!("{}", config_path.display());
                 return Ok(());
             }
+            Command::Config(ConfigCommand::Check { path }) => {
+                // Without a path, check every file the search path loads.
+                let layered = path.is_none() && args.config.is_none();
+                let paths = match path.or(args.config) {
+                    Some(path) => vec![path],
+                    None => match Config::config_files() {
+                        files if files.is_empty() => vec![
+                            Config::get_config_dir()
+                                .expect("Could not determine config directory")
+                                .join(Config::CONFIG_FILE_NAME),
+                        ],
+                        files => files,
+                    },
+                };
+                let (mut errors, mut warnings) = (0, 0);
+                for (config_path, diagnostics) in
+                    paths.iter().zip(check::check_files(&paths, layered)?)
+                {
+                    for d in &diagnostics {
+                        println!("{}:{d}", config_path.display());
+                    }
+                    let file_errors = diagnostics.iter().filter(|d| d.is_error()).count();
+                    errors += file_errors;
+                    warnings += diagnostics.len() - file_errors;
+                    if diagnostics.is_empty() {
+                        println!("{}: no problems found", config_path.display());
+                    }
+                }
+                if errors + warnings > 0 {
+                    println!("{errors} error(s), {warnings} warning(s)");
+                }
+                if errors > 0 {
+                    std::process::exit(1);
+                }
+                return Ok(());
+            }
+            Command::Config(ConfigCommand::Show { format, origins }) => {
+                let stages = config_stages(&args)?;
+                let (_, config) = stages.last().expect("the effective config is a stage");
+                let origins = if origins {
+                    Some(show::origins(&stages)?)
+                } else {
+                    None
+                };
+                if format == "json" {
+                    let config = serde_json::to_value(config)?;
+                    let json = match origins {
+                        Some(origins) => serde_json::json!({
+                            "config": config,
+                            "origins": origins
+                                .iter()
+                                .map(|(path, origin)| (path.clone(), origin.to_string().into()))
+                                .collect::<serde_json::Map<_, _>>(),
+                        }),
+                        None => config,
+                    };
+                    println!("{}", serde_json::to_string_pretty(&json)?);
+                } else {
+                    print!("{}", show::to_toml(config, origins.as_ref())?);
+                }
+                return Ok(());
+            }
+            Command::Config(ConfigCommand::Schema) => {
+                let schema = schema::config_schema();
+                println!("{}", serde_json::to_string_pretty(&schema)?);
+                return Ok(());
+            }
         }
     }
 
@@ -104,45 +188,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         unsafe { std::env::set_var("NO_COLOR", "1") };
     }
 
-    // Renderer override from flags.
-    let renderer_override = if args.json {
-        Some(RendererOverride::Json)
-    } else if args.neofetch {
-        Some(RendererOverride::Neofetch)
-    } else {
-        None
-    };
-
-    // Base config: --all preset, --no-config defaults, else load the config file.
-    let mut config = {
+    let config = {
         let _span = info_span!("config_load").entered();
-        if args.all {
-            Config::default_all()
-        } else if args.no_config {
-            Config::default()
-        } else {
-            let config_path = args.config.clone().unwrap_or_else(|| {
-                Config::get_config_dir()
-                    .expect("Could not determine config directory")
-                    .join(Config::CONFIG_FILE_NAME)
-            });
-            match config_path.try_exists() {
-                Ok(true) => Config::from_file(&config_path, None)?,
-                Ok(false) => Config::default(),
-                Err(e) => {
-                    info!("Using default config. Error checking config file: {:?}", e);
-                    Config::default()
-                }
-            }
-        }
+        load_config(&args, &config_files(&args))?
     };
 
-    // Precedence: defaults < config file < CLI flags.
-    if let Some(target) = renderer_override {
-        config = config.with_renderer(target);
-    }
-    apply_overrides(&mut config, &args);
-
     debug!("Config: {:?}", config);
 
     match config {
@@ -171,6 +221,86 @@ fn parse_u8_list(s: &str) -> Vec<u8> {
         .collect()
 }
 
+/// Renderer override from flags.
+fn renderer_override(args: &Cli) -> Option<RendererOverride> {
+    if args.json {
+        Some(RendererOverride::Json)
+    } else if args.neofetch {
+        Some(RendererOverride::Neofetch)
+    } else {
+        None
+    }
+}
+
+/// Config files to load: none for `--all`/`--no-config`, just `--config` when
+/// given (if it exists), else the search path.
+fn config_files(args: &Cli) -> Vec<PathBuf> {
+    if args.all || args.no_config {
+        Vec::new()
+    } else if let Some(config_path) = &args.config {
+        // An explicit file replaces the search path.
+        match config_path.try_exists() {
+            Ok(true) => vec![config_path.clone()],
+            Ok(false) => Vec::new(),
+            Err(e) => {
+                info!("Using default config. Error checking config file: {:?}", e);
+                Vec::new()
+            }
+        }
+    } else {
+        let files = Config::config_files();
+        debug!("Config files: {:?}", files);
+        files
+    }
+}
+
+/// The effective config: --all preset or `files` (else defaults), then flags.
+fn load_config(args: &Cli, files: &[PathBuf]) -> Result<Config, ConfigParseError> {
+    let renderer_override = renderer_override(args);
+    let mut config = if args.all {
+        Config::default_all()
+    } else {
+        load_files(args, files)?
+    };
+
+    // Precedence: defaults < config file < CLI flags. (A loaded file already
+    // picked its section for the override, so this only converts presets.)
+    if let Some(target) = renderer_override {
+        config = config.with_renderer(target);
+    }
+    apply_overrides(&mut config, args);
+    Ok(config)
+}
+
+/// Load `files`: an explicit `--config` file on its own (it has to be
+/// complete), else as search-path layers over the defaults.
+fn load_files(args: &Cli, files: &[PathBuf]) -> Result<Config, ConfigParseError> {
+    match (&args.config, files) {
+        (Some(_), [file]) => Config::from_file(file, renderer_override(args)),
+        _ => Config::from_files(files, renderer_override(args)),
+    }
+}
+
+/// The config after each layer (defaults, each config file, the flags), for
+/// `purr config show --origins`.
+fn config_stages(args: &Cli) -> Result<Vec<(Origin, Config)>, ConfigParseError> {
+    let files = config_files(args);
+    let effective = load_config(args, &files)?;
+    let defaults = match effective.renderer() {
+        RendererOverride::Neofetch => Config::default_neofetch(),
+        RendererOverride::Json => Config::default_json(),
+    };
+    let mut stages = vec![(Origin::Default, defaults)];
+    for (i, file) in files.iter().enumerate() {
+        // A layer may only load once a later one fixes it up; skip those.
+        if let Ok(config) = load_files(args, &files[..=i]) {
+            stages.push((Origin::File(file.clone()), config));
+        }
+    }
+    stages.push((Origin::CommandLine, effective));
+    Ok(stages)
+}
+
 /// Layer CLI flag overrides onto a loaded config (defaults < config < flags).
 fn apply_overrides(config: &mut Config, args: &Cli) {
     use purr_lib::config::{Backend, CoresMode, MemoryUnit, ProbeConfig, UptimeFormat};
@@ -202,11 +332,16 @@ fn apply_overrides(config: &mut Config, args: &Cli) {
         }
         if let Some(src) = &args.source {
             c.image_source = Some(src.clone());
-            c.backend = Backend::Kitty;
+            if !c.backend.is_image() {
+                c.backend = Backend::Kitty;
+            }
         }
         if let Some(b) = &args.backend {
             c.backend = match b.as_str() {
                 "kitty" => Backend::Kitty,
+                "iterm2" => Backend::Iterm2,
+                "sixel" => Backend::Sixel,
+                "blocks" => Backend::Blocks,
                 "off" => Backend::Off,
                 _ => Backend::Ascii,
             };
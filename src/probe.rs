@@ -1,9 +1,11 @@
 use std::{
+    cell::Cell,
     fmt::{self, Display, Formatter},
-    io::{BufRead, BufReader},
+    io::{BufRead, BufReader, Read},
     path::PathBuf,
-    process::Command,
-    sync::OnceLock,
+    process::{Command, Output, Stdio},
+    sync::{Arc, OnceLock, mpsc},
+    time::{Duration, Instant},
 };
 
 use tracing::{debug_span, instrument};
@@ -20,8 +22,9 @@ use sysinfo::{Disks, Users};
 use thiserror::Error;
 
 use crate::config::{
-    CoresMode, CpuOptions, DeOptions, DiskOptions, DistroOptions, GpuOptions, GpuType,
-    KernelOptions, MemoryOptions, PackagesOptions, ShellOptions, SongOptions, SpeedType,
+    BiosOptions, BluetoothOptions, CommandOptions, CoresMode, CpuOptions, DeOptions, DiskOptions,
+    DistroOptions, GpuOptions, GpuType, KernelOptions, MemoryOptions, NetworkOptions,
+    PackagesOptions, ResolutionOptions, ShellOptions, SongOptions, SpeedType, TempUnit,
     UptimeOptions,
 };
 
@@ -90,11 +93,7 @@ pub fn network_readout() -> &'static NetworkReadout {
 
 /// Run `gsettings get <schema> <key>` and return the trimmed output with surrounding quotes removed.
 fn gsettings_get(schema: &str, key: &str) -> Result<String, ProbeError> {
-    let _span = debug_span!("subprocess", cmd = "gsettings").entered();
-    let output = Command::new("gsettings")
-        .args(["get", schema, key])
-        .output()
-        .map_err(|_| ProbeError::MetricsUnavailable)?;
+    let output = command_output("gsettings", &["get", schema, key])?;
     if !output.status.success() {
         return Err(ProbeError::MetricsUnavailable);
     }
@@ -108,13 +107,94 @@ fn gsettings_get(schema: &str, key: &str) -> Result<String, ProbeError> {
     Ok(value)
 }
 
-/// Run a command and return stdout trimmed, or Err if it fails or produces empty output.
-fn run_command(cmd: &str, args: &[&str]) -> Result<String, ProbeError> {
+thread_local! {
+    /// Deadline of the probe running on this thread (set by [`with_deadline`]).
+    static PROBE_DEADLINE: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+/// Run `f` with `deadline` as this thread's probe deadline: subprocesses it
+/// spawns through [`run_command`] are killed once the deadline passes.
+pub fn with_deadline<R>(deadline: Option<Instant>, f: impl FnOnce() -> R) -> R {
+    /// Restores the previous deadline even when `f` panics.
+    struct Restore(Option<Instant>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            PROBE_DEADLINE.set(self.0);
+        }
+    }
+    let _restore = Restore(PROBE_DEADLINE.replace(deadline));
+    f()
+}
+
+/// Run a command to completion and collect its output. Under a probe deadline
+/// the child is killed (and `ProbeError::TimedOut` returned) if it outlives it.
+fn command_output(cmd: &str, args: &[&str]) -> Result<Output, ProbeError> {
     let _span = debug_span!("subprocess", cmd = %cmd).entered();
-    let output = Command::new(cmd)
-        .args(args)
-        .output()
+    let mut command = Command::new(cmd);
+    command.args(args);
+    let Some(deadline) = PROBE_DEADLINE.get() else {
+        return command.output().map_err(|_| ProbeError::MetricsUnavailable);
+    };
+
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
         .map_err(|_| ProbeError::MetricsUnavailable)?;
+    // Drain both pipes off-thread so a chatty child can't block on a full pipe
+    // while we wait on the deadline.
+    let (tx, rx) = mpsc::channel();
+    for (stream, pipe) in [
+        (
+            0,
+            child
+                .stdout
+                .take()
+                .map(|p| Box::new(p) as Box<dyn Read + Send>),
+        ),
+        (
+            1,
+            child
+                .stderr
+                .take()
+                .map(|p| Box::new(p) as Box<dyn Read + Send>),
+        ),
+    ] {
+        let tx = tx.clone();
+        std::thread::spawn(move || {
+            let mut buf = Vec::new();
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_end(&mut buf);
+            }
+            let _ = tx.send((stream, buf));
+        });
+    }
+    drop(tx);
+
+    let (mut stdout, mut stderr) = (Vec::new(), Vec::new());
+    for _ in 0..2 {
+        match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+            Ok((0, buf)) => stdout = buf,
+            Ok((_, buf)) => stderr = buf,
+            Err(_) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(ProbeError::TimedOut);
+            }
+        }
+    }
+    let status = child.wait().map_err(|_| ProbeError::MetricsUnavailable)?;
+    Ok(Output {
+        status,
+        stdout,
+        stderr,
+    })
+}
+
+/// Run a command and return stdout trimmed, or Err if it fails or produces empty output.
+fn run_command(cmd: &str, args: &[&str]) -> Result<String, ProbeError> {
+    let output = command_output(cmd, args)?;
     if !output.status.success() {
         return Err(ProbeError::MetricsUnavailable);
     }
@@ -339,6 +419,83 @@ fn with_gtk_tag(value: &str, from_gsettings: bool) -> String {
     format!("{value} {tag}")
 }
 
+/// Resolve the cursor theme and size, trying in order: `XCURSOR_THEME`/
+/// `XCURSOR_SIZE`, KDE's `kcminputrc` (checked before gsettings since GNOME's
+/// schema — and its "Adwaita" default — is often installed on Plasma systems
+/// too), gsettings, the gtk-3.0 settings.ini, `~/.Xresources`, and finally the theme
+/// `~/.icons/default/index.theme` inherits. GTK sources carry the same
+/// `[GTK…]` tag as Theme/Icons.
+#[instrument(level = "debug")]
+fn detect_cursor() -> Result<String, ProbeError> {
+    let home = std::env::var("HOME").ok();
+    let path = |rel: &str| home.as_ref().map(|h| PathBuf::from(format!("{h}/{rel}")));
+
+    // An explicit override beats whatever the desktop settings say.
+    if let Ok(theme) = std::env::var("XCURSOR_THEME")
+        && !theme.is_empty()
+    {
+        let size = std::env::var("XCURSOR_SIZE").ok();
+        return Ok(format_cursor(&theme, size.as_deref(), None));
+    }
+    if let Some(kcm) = path(".config/kcminputrc")
+        && let Ok(theme) = parse_ini_key(&kcm, "cursorTheme")
+    {
+        let size = parse_ini_key(&kcm, "cursorSize").ok();
+        return Ok(format_cursor(&theme, size.as_deref(), None));
+    }
+    if let Ok(theme) = gsettings_get("org.gnome.desktop.interface", "cursor-theme") {
+        let size = gsettings_get("org.gnome.desktop.interface", "cursor-size").ok();
+        return Ok(format_cursor(&theme, size.as_deref(), Some(true)));
+    }
+    if let Some(ini) = path(".config/gtk-3.0/settings.ini")
+        && let Ok(theme) = parse_ini_key(&ini, "gtk-cursor-theme-name")
+    {
+        let size = parse_ini_key(&ini, "gtk-cursor-theme-size").ok();
+        return Ok(format_cursor(&theme, size.as_deref(), Some(false)));
+    }
+    if let Some(xres) = path(".Xresources").and_then(|p| std::fs::read_to_string(p).ok())
+        && let Some(theme) = parse_xresource(&xres, "Xcursor.theme")
+    {
+        let size = parse_xresource(&xres, "Xcursor.size");
+        return Ok(format_cursor(&theme, size.as_deref(), None));
+    }
+    let index = path(".icons/default/index.theme").ok_or(ProbeError::MetricsUnavailable)?;
+    let theme = parse_ini_key(&index, "Inherits")?;
+    Ok(format_cursor(&theme, None, None))
+}
+
+/// Look up `key` (e.g. "Xcursor.theme") in X resources syntax
+/// (`Xcursor.theme: Adwaita`), skipping `!` comments.
+fn parse_xresource(content: &str, key: &str) -> Option<String> {
+    content.lines().find_map(|line| {
+        let line = line.trim();
+        if line.starts_with('!') {
+            return None;
+        }
+        let (k, v) = line.split_once(':')?;
+        // `*Xcursor.theme` / `*.Xcursor.theme` wildcard bindings also apply.
+        (k.trim().trim_start_matches(['*', '.']) == key)
+            .then(|| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    })
+}
+
+/// Render "theme (size px)", appending the `[GTK…]` tag for GTK sources
+/// (`Some(from_gsettings)`, see [`with_gtk_tag`]).
+fn format_cursor(theme: &str, size: Option<&str>, gtk: Option<bool>) -> String {
+    let mut s = theme.to_string();
+    if let Some(px) = size
+        .map(str::trim)
+        .filter(|p| p.parse::<u32>().is_ok_and(|n| n > 0))
+    {
+        s.push_str(&format!(" ({px}px)"));
+    }
+    match gtk {
+        Some(from_gsettings) => with_gtk_tag(&s, from_gsettings),
+        None => s,
+    }
+}
+
 /// Apply neofetch's window-manager renames (`*GNOME*Shell*` -> "Mutter",
 /// `*WINDOWMAKER*` -> "wmaker"). Done case-insensitively so libmacchina's
 /// lowercase process name "gnome-shell" maps to "Mutter" like neofetch.
@@ -415,9 +572,14 @@ fn detect_terminal_font() -> Result<String, ProbeError> {
 }
 
 // TODO: Complete the rest of doc comments for this enum vv
+/// Serializes as the raw typed data (JSON `data`): single-value probes as the
+/// bare value, multi-field ones as an object of named fields (see [`data`]).
+#[derive(Serialize)]
+#[serde(untagged)]
 pub enum ProbeValue {
     /// Hostname (username@hostname)
     /// e.g. ("justin13888", "purr")
+    #[serde(serialize_with = "data::host")]
     Host(String, String),
     /// e.g. "Ubuntu 22.04.4 LTS (Jammy Jellyfish)"
     OS(String),
@@ -427,25 +589,32 @@ pub enum ProbeValue {
     /// Model
     // (Vendor, Product)
     /// e.g. ("Dell Inc.", "XPS 15 9510")
+    #[serde(serialize_with = "data::model")]
     Model(String, String),
     /// e.g. "6.8.4-cachyos"
     Kernel(String),
     /// Uptime in seconds
     /// E.g. 123
+    #[serde(serialize_with = "data::uptime")]
     Uptime(usize),
     /// Number of packages installed
     /// Vec<(package manager, count)>
     /// E.g. [("dpkg", 123)]
+    #[serde(serialize_with = "data::packages")]
     Packages(Vec<(String, usize)>),
     /// E.g. "zsh 5.8.1"
     /// Shell name + optional version, e.g. ("zsh", Some("5.9")).
+    #[serde(serialize_with = "data::shell")]
     Shell(String, Option<String>),
     /// E.g. "vim 8.2" // TODO: CHECK THIS example
     Editor(String),
-    /// E.g. "1920x1080"
-    Resolution(String),
+    /// Display mode + refresh rate in Hz, when known
+    /// E.g. ("1920x1080", Some(60.0))
+    #[serde(serialize_with = "data::resolution")]
+    Resolution(String, Option<f64>),
     /// E.g. "GNOME", "hyprland", "Fluent" (Windows)
     /// Desktop environment name + optional version, e.g. ("GNOME", Some("46.0")).
+    #[serde(serialize_with = "data::de")]
     DE(String, Option<String>),
     /// E.g. "Mutter"
     WM(String),
@@ -454,30 +623,49 @@ pub enum ProbeValue {
     /// E.g. "Adwaita-dark" // TODO: CHECK
     Theme(String),
     Icons(String),
+    /// Cursor theme with size and GTK tag where known
+    /// E.g. "Adwaita (24px) [GTK2/3]"
     Cursor(String),
     Terminal(String),
     TerminalFont(String),
-    /// E.g. "Intel Core i7-11800H"
-    CPU(String),
+    /// Model name + package temperature in °C, read when the probe's `temp`
+    /// is on
+    /// E.g. ("Intel Core i7-11800H", Some(49.2))
+    #[serde(serialize_with = "data::cpu")]
+    CPU(String, Option<f64>),
     /// E.g. "NVIDIA GeForce RTX 4090", "Intel(R) UHD Graphics"
     GPU(String),
-    /// Amount of memory (in MiB)
+    /// Amount of memory (in KiB)
     /// (used, total)
-    /// E.g. (46863, 64290)
+    /// E.g. (47987712, 65832960)
+    #[serde(serialize_with = "data::memory")]
     Memory(u64, u64),
-    Network(String),
-    Bluetooth(String),
-    BIOS(String),
+    /// Link details for one network interface
+    /// E.g. enp5s0, up, 1000 Mb/s, wired
+    Network(NetInterface),
+    /// A Bluetooth adapter or connected device, per `BluetoothOptions::mode`
+    /// E.g. Device { "WH-1000XM4", Some(80) }
+    Bluetooth(BluetoothItem),
+    /// Firmware details from DMI plus boot mode
+    /// E.g. ("LENOVO", "N32ET91W (1.67)", "05/14/2024", UEFI, Secure Boot off)
+    BIOS(BiosInfo),
     /// E.g. "bochs-drm"
     GPUDriver(String),
     /// CPU usage percentage
     /// E.g. 12
+    #[serde(serialize_with = "data::cpu_usage")]
     CPUUsage(usize),
+    /// CPU package temperature in °C
+    /// E.g. 49.2
+    #[serde(serialize_with = "data::cpu_temp")]
+    CpuTemp(f64),
     /// Disk usage (in bytes)
     /// (mountpoint, device name, used, total)
+    #[serde(serialize_with = "data::disk")]
     Disk(PathBuf, String, u64, u64),
     /// Battery percentage
     /// E.g. 86
+    #[serde(serialize_with = "data::battery")]
     Battery(u8), // TODO: CHECK
     PowerAdapter(String), // TODO: CHECK
     Font(String),
@@ -500,6 +688,100 @@ pub enum ProbeValue {
     /// Rust version
     /// E.g. "rustc 1.57.0"
     Rust(String),
+    /// One line of a user-defined command's output
+    /// E.g. "build 2024.06.1"
+    Command(String),
+    /// Fixed text from a layout entry (`Text`, or a `Header`'s text)
+    Text(String),
+}
+
+/// Named-field serializers for [`ProbeValue`]'s multi-field (and unit-bearing)
+/// variants, so typed JSON reads `{"used_kib": …, "total_kib": …}` rather than
+/// a positional array.
+mod data {
+    use std::path::PathBuf;
+
+    use serde::{Serializer, ser::SerializeMap, ser::SerializeStruct};
+
+    /// `fn name(field: &Type, …, serializer)` emitting `{ "field": … }`.
+    macro_rules! named_fields {
+        ($($name:ident($($field:ident: $ty:ty),+);)*) => {$(
+            pub fn $name<S: Serializer>($($field: &$ty,)+ s: S) -> Result<S::Ok, S::Error> {
+                let mut st = s.serialize_struct(stringify!($name), [$(stringify!($field)),+].len())?;
+                $(st.serialize_field(stringify!($field), $field)?;)+
+                st.end()
+            }
+        )*};
+    }
+
+    named_fields! {
+        host(username: String, hostname: String);
+        model(vendor: String, product: String);
+        uptime(seconds: usize);
+        shell(name: String, version: Option<String>);
+        resolution(mode: String, refresh_hz: Option<f64>);
+        de(name: String, version: Option<String>);
+        cpu(model: String, temp_celsius: Option<f64>);
+        memory(used_kib: u64, total_kib: u64);
+        cpu_usage(percent: usize);
+        cpu_temp(celsius: f64);
+        disk(mount: PathBuf, device: String, used_bytes: u64, total_bytes: u64);
+        battery(percent: u8);
+    }
+
+    /// Package counts as a `{ manager: count }` object.
+    pub fn packages<S: Serializer>(counts: &[(String, usize)], s: S) -> Result<S::Ok, S::Error> {
+        let mut map = s.serialize_map(Some(counts.len()))?;
+        for (manager, count) in counts {
+            map.serialize_entry(manager, count)?;
+        }
+        map.end()
+    }
+}
+
+/// Link details for one network interface, as read from `/sys/class/net`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct NetInterface {
+    /// Interface name, e.g. "enp5s0"
+    pub name: String,
+    /// Kernel `operstate`, e.g. "up", "down", "dormant"
+    pub state: String,
+    /// Hardware address, e.g. "a8:a1:59:12:34:56"
+    pub mac: Option<String>,
+    /// Negotiated link speed in Mb/s; absent for down or wireless links
+    pub speed_mbps: Option<u32>,
+    pub mtu: Option<u32>,
+    pub wireless: bool,
+    pub loopback: bool,
+}
+
+/// Firmware details, as read from DMI sysfs and `/sys/firmware/efi`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct BiosInfo {
+    /// `bios_vendor`, e.g. "American Megatrends Inc."
+    pub vendor: Option<String>,
+    /// `bios_version`, e.g. "1.A0"
+    pub version: Option<String>,
+    /// `bios_date`, e.g. "03/12/2024"
+    pub date: Option<String>,
+    /// Booted via UEFI (`/sys/firmware/efi` exists) rather than legacy BIOS
+    pub uefi: bool,
+    /// Secure Boot state from efivars; `None` when unknown (e.g. legacy boot)
+    pub secure_boot: Option<bool>,
+}
+
+/// A BlueZ adapter or connected device.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BluetoothItem {
+    /// e.g. { "hci0", "desktop", true }
+    Adapter {
+        id: String,
+        name: String,
+        powered: bool,
+    },
+    /// Device alias plus battery percentage (when it exposes `Battery1`)
+    Device { name: String, battery: Option<u8> },
 }
 
 #[derive(Error, Debug)]
@@ -513,6 +795,21 @@ pub enum ProbeError {
     Other(String),
     /// Metric readout might be erroneous
     Warning(String),
+    /// Probe did not finish before its deadline
+    TimedOut,
+}
+
+impl ProbeError {
+    /// Stable machine-readable name of the variant (used by JSON output).
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ProbeError::MetricsUnavailable => "metrics_unavailable",
+            ProbeError::Unimplemented => "unimplemented",
+            ProbeError::Other(_) => "other",
+            ProbeError::Warning(_) => "warning",
+            ProbeError::TimedOut => "timed_out",
+        }
+    }
 }
 
 impl Display for ProbeError {
@@ -522,6 +819,7 @@ impl Display for ProbeError {
             ProbeError::Unimplemented => write!(f, "Unimplemented"),
             ProbeError::Other(s) => write!(f, "{}", s),
             ProbeError::Warning(s) => write!(f, "{}", s),
+            ProbeError::TimedOut => write!(f, "Timed out"),
         }
     }
 }
@@ -550,41 +848,46 @@ impl From<ProbeType> for ProbeResultFunction {
         use libmacchina::traits::PackageReadout as _;
 
         match probe_type {
-            ProbeType::Host => Box::new(|| {
+            ProbeType::Host => Arc::new(|| {
                 Ok(ProbeResultValue::Single(ProbeValue::Host(
                     general_readout().username()?,
                     general_readout().hostname()?,
-                )))
+                ))
+                .into())
             }),
-            ProbeType::OS => Box::new(|| {
-                // macOS: build the OS string the way neofetch does — codename
-                // ("macOS" for modern releases) + `sw_vers` product version +
-                // build. The architecture is appended later by `DistroOptions`.
-                #[cfg(target_os = "macos")]
-                {
-                    let version = sw_vers("-productVersion")?;
-                    let build = sw_vers("-buildVersion")?;
-                    Ok(ProbeResultValue::Single(ProbeValue::OS(format!(
-                        "macOS {version} {build}"
-                    ))))
-                }
-                // libmacchina's `os_name` is unimplemented on Linux, so fall back to the
-                // distribution pretty-name (e.g. "Fedora Linux 44 (Silverblue)"). The
-                // architecture and shorthand are applied later by `DistroOptions`.
-                #[cfg(not(target_os = "macos"))]
-                {
-                    let name = general_readout()
-                        .os_name()
-                        .or_else(|_| general_readout().distribution())?;
-                    Ok(ProbeResultValue::Single(ProbeValue::OS(name)))
-                }
-            }),
-            ProbeType::Distro => Box::new(|| {
-                Ok(ProbeResultValue::Single(ProbeValue::Distro(
-                    general_readout().distribution()?,
-                )))
+            ProbeType::OS => {
+                Arc::new(|| {
+                    // macOS: build the OS string the way neofetch does — codename
+                    // ("macOS" for modern releases) + `sw_vers` product version +
+                    // build. The architecture is appended later by `DistroOptions`.
+                    #[cfg(target_os = "macos")]
+                    {
+                        let version = sw_vers("-productVersion")?;
+                        let build = sw_vers("-buildVersion")?;
+                        Ok(ProbeResultValue::Single(ProbeValue::OS(format!(
+                            "macOS {version} {build}"
+                        )))
+                        .into())
+                    }
+                    // libmacchina's `os_name` is unimplemented on Linux, so fall back to the
+                    // distribution pretty-name (e.g. "Fedora Linux 44 (Silverblue)"). The
+                    // architecture and shorthand are applied later by `DistroOptions`.
+                    #[cfg(not(target_os = "macos"))]
+                    {
+                        let name = general_readout()
+                            .os_name()
+                            .or_else(|_| general_readout().distribution())?;
+                        Ok(ProbeResultValue::Single(ProbeValue::OS(name)).into())
+                    }
+                })
+            }
+            ProbeType::Distro => Arc::new(|| {
+                Ok(
+                    ProbeResultValue::Single(ProbeValue::Distro(general_readout().distribution()?))
+                        .into(),
+                )
             }),
-            ProbeType::Model => Box::new(|| {
+            ProbeType::Model => Arc::new(|| {
                 // macOS reports just the model identifier (`sysctl hw.model`,
                 // e.g. "Mac15,7"), with no vendor, matching neofetch.
                 #[cfg(target_os = "macos")]
@@ -592,7 +895,8 @@ impl From<ProbeType> for ProbeResultFunction {
                     Ok(ProbeResultValue::Single(ProbeValue::Model(
                         String::new(),
                         clean_model(&sysctl("hw.model")?),
-                    )))
+                    ))
+                    .into())
                 }
                 // neofetch sources the model from DMI sysfs — preferring the
                 // motherboard (`board_vendor`/`board_name`) over the chassis
@@ -610,23 +914,28 @@ impl From<ProbeType> for ProbeResultFunction {
                         Some(model) => Ok(ProbeResultValue::Single(ProbeValue::Model(
                             String::new(),
                             model,
-                        ))),
+                        ))
+                        .into()),
                         None => Err(ProbeError::MetricsUnavailable),
                     }
                 }
             }),
-            ProbeType::Kernel => Box::new(|| {
-                Ok(ProbeResultValue::Single(ProbeValue::Kernel(
-                    kernel_readout().os_release()?,
-                )))
+            ProbeType::Kernel => Arc::new(|| {
+                Ok(
+                    ProbeResultValue::Single(ProbeValue::Kernel(kernel_readout().os_release()?))
+                        .into(),
+                )
             }),
-            ProbeType::Uptime => Box::new(|| {
-                Ok(ProbeResultValue::Single(ProbeValue::Uptime(
-                    general_readout().uptime()?,
-                )))
+            ProbeType::Uptime => Arc::new(|| {
+                Ok(
+                    ProbeResultValue::Single(ProbeValue::Uptime(general_readout().uptime()?))
+                        .into(),
+                )
             }),
-            // TODO: Test libmacchina packages() function for package manager hanging issues
-            ProbeType::Packages => Box::new(|| {
+            // A hanging package manager is bounded by the probe deadline: the
+            // renderer reports the probe as timed out and moves on. libmacchina
+            // spawns the managers itself, so those are abandoned, not killed.
+            ProbeType::Packages => Arc::new(|| {
                 let _span = debug_span!("pkg_count").entered();
                 // macOS: count MacPorts + Homebrew (Cellar) like neofetch;
                 // libmacchina otherwise includes casks/cargo and mislabels brew.
@@ -638,9 +947,9 @@ impl From<ProbeType> for ProbeResultFunction {
                     .into_iter()
                     .map(|(name, count)| (name.to_string(), count))
                     .collect::<Vec<_>>();
-                Ok(ProbeResultValue::Single(ProbeValue::Packages(counts)))
+                Ok(ProbeResultValue::Single(ProbeValue::Packages(counts)).into())
             }),
-            ProbeType::Shell => Box::new(|| {
+            ProbeType::Shell => Arc::new(|| {
                 // macOS: libmacchina's `shell()` errors, so derive the name from
                 // `$SHELL` like neofetch (`${SHELL##*/}`).
                 #[cfg(target_os = "macos")]
@@ -657,35 +966,27 @@ impl From<ProbeType> for ProbeResultFunction {
                 // Gather the version here (in the parallel probe thread) so the
                 // renderer never shells out on its hot path.
                 let version = shell_version(&name);
-                Ok(ProbeResultValue::Single(ProbeValue::Shell(name, version)))
+                Ok(ProbeResultValue::Single(ProbeValue::Shell(name, version)).into())
             }),
-            ProbeType::Editor => Box::new(|| {
+            ProbeType::Editor => Arc::new(|| {
                 let editor = std::env::var("VISUAL")
                     .or_else(|_| std::env::var("EDITOR"))
                     .map_err(|_| ProbeError::MetricsUnavailable)?;
-                Ok(ProbeResultValue::Single(ProbeValue::Editor(editor)))
-            }),
-            ProbeType::Resolution => Box::new(|| {
-                let raw = general_readout().resolution()?;
-                // macOS: neofetch shows only the logical/scaled resolution
-                // (e.g. "1728x1117"), no native-mode or refresh-rate suffix.
-                #[cfg(target_os = "macos")]
-                let value = scaled_resolution(&raw);
-                #[cfg(not(target_os = "macos"))]
-                let value = raw;
-                Ok(ProbeResultValue::Single(ProbeValue::Resolution(value)))
+                Ok(ProbeResultValue::Single(ProbeValue::Editor(editor)).into())
             }),
-            ProbeType::DE => Box::new(|| {
+            ProbeType::Resolution => resolution_probe_fn(ResolutionOptions::default()),
+            ProbeType::DE => Arc::new(|| {
                 let name = general_readout().desktop_environment()?;
                 let version = de_version(&name);
-                Ok(ProbeResultValue::Single(ProbeValue::DE(name, version)))
+                Ok(ProbeResultValue::Single(ProbeValue::DE(name, version)).into())
             }),
-            ProbeType::WM => Box::new(|| {
+            ProbeType::WM => Arc::new(|| {
                 Ok(ProbeResultValue::Single(ProbeValue::WM(normalize_wm(
                     &general_readout().window_manager()?,
-                ))))
+                )))
+                .into())
             }),
-            ProbeType::WMTheme => Box::new(|| {
+            ProbeType::WMTheme => Arc::new(|| {
                 // macOS: "<accent> (<interface style>)", e.g. "Blue (Dark)", from
                 // the global AppleAccentColor / AppleInterfaceStyle preferences.
                 #[cfg(target_os = "macos")]
@@ -693,9 +994,12 @@ impl From<ProbeType> for ProbeResultFunction {
                     let style = defaults_read_global("AppleInterfaceStyle")
                         .unwrap_or_else(|| "Light".to_string());
                     let accent = map_accent(defaults_read_global("AppleAccentColor").as_deref());
-                    Ok(ProbeResultValue::Single(ProbeValue::WMTheme(format!(
-                        "{accent} ({style})"
-                    ))))
+                    Ok(
+                        ProbeResultValue::Single(ProbeValue::WMTheme(format!(
+                            "{accent} ({style})"
+                        )))
+                        .into(),
+                    )
                 }
                 // GNOME (Fedora/Ubuntu default): query WM preferences theme via gsettings
                 // Fallback: parse GTK3 settings.ini
@@ -713,21 +1017,24 @@ impl From<ProbeType> for ProbeResultFunction {
                                 "gtk-theme-name",
                             )
                         })?;
-                    Ok(ProbeResultValue::Single(ProbeValue::WMTheme(theme)))
+                    Ok(ProbeResultValue::Single(ProbeValue::WMTheme(theme)).into())
                 }
             }),
-            ProbeType::Theme => Box::new(|| {
+            ProbeType::Theme => Arc::new(|| {
                 // GTK theme via gsettings (GNOME), falling back to the gtk-3.0
                 // settings.ini, with neofetch's `[GTK2/3]`/`[GTK3]` tag.
                 let theme = gtk_style("gtk-theme", "gtk-theme-name")?;
-                Ok(ProbeResultValue::Single(ProbeValue::Theme(theme)))
+                Ok(ProbeResultValue::Single(ProbeValue::Theme(theme)).into())
             }),
-            ProbeType::Icons => Box::new(|| {
+            ProbeType::Icons => Arc::new(|| {
                 let icons = gtk_style("icon-theme", "gtk-icon-theme-name")?;
-                Ok(ProbeResultValue::Single(ProbeValue::Icons(icons)))
+                Ok(ProbeResultValue::Single(ProbeValue::Icons(icons)).into())
+            }),
+            ProbeType::Cursor => Arc::new(|| {
+                let cursor = detect_cursor()?;
+                Ok(ProbeResultValue::Single(ProbeValue::Cursor(cursor)).into())
             }),
-            ProbeType::Cursor => Box::new(|| Err(ProbeError::Unimplemented)),
-            ProbeType::Terminal => Box::new(|| {
+            ProbeType::Terminal => Arc::new(|| {
                 // macOS: neofetch derives the terminal from `$TERM_PROGRAM`
                 // (no version). Fall back to libmacchina, stripping its version
                 // suffix, when the variable is unset.
@@ -740,26 +1047,23 @@ impl From<ProbeType> for ProbeResultFunction {
                             t.split(" (Version").next().unwrap_or(&t).trim().to_string()
                         }
                     };
-                    Ok(ProbeResultValue::Single(ProbeValue::Terminal(term)))
+                    Ok(ProbeResultValue::Single(ProbeValue::Terminal(term)).into())
                 }
                 #[cfg(not(target_os = "macos"))]
                 {
                     Ok(ProbeResultValue::Single(ProbeValue::Terminal(
                         general_readout().terminal()?.trim().to_string(),
-                    )))
+                    ))
+                    .into())
                 }
             }),
-            ProbeType::TerminalFont => Box::new(|| {
+            ProbeType::TerminalFont => Arc::new(|| {
                 let font = detect_terminal_font()?;
-                Ok(ProbeResultValue::Single(ProbeValue::TerminalFont(font)))
-            }),
-            ProbeType::CPU => Box::new(|| {
-                Ok(ProbeResultValue::Single(ProbeValue::CPU(
-                    general_readout().cpu_model_name()?,
-                )))
+                Ok(ProbeResultValue::Single(ProbeValue::TerminalFont(font)).into())
             }),
+            ProbeType::CPU => cpu_probe_fn(CpuOptions::default()),
             ProbeType::GPU => gpu_probe_fn(GpuOptions::default()),
-            ProbeType::Memory => Box::new(|| {
+            ProbeType::Memory => Arc::new(|| {
                 // macOS: used = (wired + active + compressed) pages. neofetch uses
                 // the same components but hardcodes a 4 KiB page size, which
                 // undercounts ~4x on Apple Silicon (16 KiB pages); purr uses the
@@ -778,47 +1082,53 @@ impl From<ProbeType> for ProbeResultFunction {
                         .or_else(|| sysctl("hw.pagesize").ok()?.parse::<u64>().ok())
                         .unwrap_or(4096);
                     let used_kib = (wired + active + compressed) * page_bytes / 1024;
-                    Ok(ProbeResultValue::Single(ProbeValue::Memory(
-                        used_kib, total_kib,
-                    )))
+                    Ok(ProbeResultValue::Single(ProbeValue::Memory(used_kib, total_kib)).into())
                 }
                 #[cfg(not(target_os = "macos"))]
                 {
                     Ok(ProbeResultValue::Single(ProbeValue::Memory(
                         memory_readout().used()?,
                         memory_readout().total()?,
-                    )))
+                    ))
+                    .into())
                 }
             }),
-            ProbeType::Network => Box::new(|| Err(ProbeError::Unimplemented)),
-            ProbeType::Bluetooth => Box::new(|| Err(ProbeError::Unimplemented)),
-            ProbeType::BIOS => Box::new(|| Err(ProbeError::Unimplemented)),
-            ProbeType::GPUDriver => Box::new(|| {
+            ProbeType::Network => network_probe_fn(NetworkOptions::default()),
+            ProbeType::Bluetooth => bluetooth_probe_fn(BluetoothOptions::default()),
+            ProbeType::BIOS => bios_probe_fn(),
+            ProbeType::GPUDriver => Arc::new(|| {
                 let driver = gpu_driver().ok_or(ProbeError::MetricsUnavailable)?;
-                Ok(ProbeResultValue::Single(ProbeValue::GPUDriver(driver)))
+                Ok(ProbeResultValue::Single(ProbeValue::GPUDriver(driver)).into())
             }),
-            ProbeType::CPUUsage => Box::new(|| {
+            ProbeType::CPUUsage => Arc::new(|| {
                 let _span = debug_span!("cpu_usage_poll").entered();
-                Ok(ProbeResultValue::Single(ProbeValue::CPUUsage(
-                    general_readout().cpu_usage()?,
-                )))
+                Ok(
+                    ProbeResultValue::Single(ProbeValue::CPUUsage(general_readout().cpu_usage()?))
+                        .into(),
+                )
+            }),
+            ProbeType::CpuTemp => Arc::new(|| {
+                let celsius = cpu_temp_celsius().ok_or(ProbeError::MetricsUnavailable)?;
+                Ok(ProbeResultValue::Single(ProbeValue::CpuTemp(celsius)).into())
             }),
             ProbeType::Disk => disk_probe_fn(DiskOptions::default()),
-            ProbeType::Battery => Box::new(|| {
-                Ok(ProbeResultValue::Single(ProbeValue::Battery(
-                    battery_readout().percentage()?,
-                )))
+            ProbeType::Battery => Arc::new(|| {
+                Ok(
+                    ProbeResultValue::Single(ProbeValue::Battery(battery_readout().percentage()?))
+                        .into(),
+                )
             }),
             // TODO: Check if it's correct and matches neofetch
-            ProbeType::PowerAdapter => Box::new(|| {
+            ProbeType::PowerAdapter => Arc::new(|| {
                 Ok(ProbeResultValue::Single(ProbeValue::PowerAdapter(
                     match battery_readout().status()? {
                         BatteryState::Charging => "Charging".to_string(),
                         BatteryState::Discharging => "Discharging".to_string(),
                     },
-                )))
+                ))
+                .into())
             }),
-            ProbeType::Font => Box::new(|| {
+            ProbeType::Font => Arc::new(|| {
                 // Mirror Theme/Icons: GNOME interface font, then GTK3 settings.ini.
                 let font =
                     gsettings_get("org.gnome.desktop.interface", "font-name").or_else(|_| {
@@ -829,26 +1139,28 @@ impl From<ProbeType> for ProbeResultFunction {
                             "gtk-font-name",
                         )
                     })?;
-                Ok(ProbeResultValue::Single(ProbeValue::Font(font)))
+                Ok(ProbeResultValue::Single(ProbeValue::Font(font)).into())
             }),
             ProbeType::Song => song_probe_fn(SongOptions::default()),
-            ProbeType::LocalIP => Box::new(|| {
+            ProbeType::Command => command_probe_fn(CommandOptions::default()),
+            ProbeType::LocalIP => Arc::new(|| {
                 // macOS: neofetch reads `ipconfig getifaddr en0` (then en1).
                 #[cfg(target_os = "macos")]
                 {
                     let ip = run_command("ipconfig", &["getifaddr", "en0"])
                         .or_else(|_| run_command("ipconfig", &["getifaddr", "en1"]))?;
-                    Ok(ProbeResultValue::Single(ProbeValue::LocalIP(ip)))
+                    Ok(ProbeResultValue::Single(ProbeValue::LocalIP(ip)).into())
                 }
                 #[cfg(not(target_os = "macos"))]
                 {
                     Ok(ProbeResultValue::Single(ProbeValue::LocalIP(
                         network_readout().logical_address(None)?,
-                    )))
+                    ))
+                    .into())
                 }
             }),
-            ProbeType::PublicIP => Box::new(|| Err(ProbeError::Unimplemented)),
-            ProbeType::Users => Box::new(|| {
+            ProbeType::PublicIP => Arc::new(|| Err(ProbeError::Unimplemented)),
+            ProbeType::Users => Arc::new(|| {
                 let _span = debug_span!("user_scan").entered();
                 Ok(ProbeResultValue::Single(ProbeValue::Users(
                     // TODO: Evaluate, whether we should make determining user platform dependent (may be unreliable currently)
@@ -872,21 +1184,18 @@ impl From<ProbeType> for ProbeResultFunction {
                         })
                         .map(|user| user.name().to_string())
                         .collect::<Vec<_>>(),
-                )))
+                ))
+                .into())
             }),
-            ProbeType::Locale => Box::new(|| {
+            ProbeType::Locale => Arc::new(|| {
                 let locale = std::env::var("LANG")
                     .or_else(|_| std::env::var("LC_ALL"))
                     .map_err(|_| ProbeError::MetricsUnavailable)?;
-                Ok(ProbeResultValue::Single(ProbeValue::Locale(locale)))
+                Ok(ProbeResultValue::Single(ProbeValue::Locale(locale)).into())
             }),
-            ProbeType::Java => Box::new(|| {
+            ProbeType::Java => Arc::new(|| {
                 // java -version writes to stderr
-                let _span = debug_span!("subprocess", cmd = "java").entered();
-                let output = Command::new("java")
-                    .arg("-version")
-                    .output()
-                    .map_err(|_| ProbeError::MetricsUnavailable)?;
+                let output = command_output("java", &["-version"])?;
                 let version = String::from_utf8_lossy(&output.stderr)
                     .lines()
                     .next()
@@ -896,20 +1205,20 @@ impl From<ProbeType> for ProbeResultFunction {
                 if version.is_empty() {
                     return Err(ProbeError::MetricsUnavailable);
                 }
-                Ok(ProbeResultValue::Single(ProbeValue::Java(version)))
+                Ok(ProbeResultValue::Single(ProbeValue::Java(version)).into())
             }),
-            ProbeType::Python => Box::new(|| {
+            ProbeType::Python => Arc::new(|| {
                 let version = run_command("python3", &["--version"])
                     .or_else(|_| run_command("python", &["--version"]))?;
-                Ok(ProbeResultValue::Single(ProbeValue::Python(version)))
+                Ok(ProbeResultValue::Single(ProbeValue::Python(version)).into())
             }),
-            ProbeType::Node => Box::new(|| {
+            ProbeType::Node => Arc::new(|| {
                 let version = run_command("node", &["--version"])?;
-                Ok(ProbeResultValue::Single(ProbeValue::Node(version)))
+                Ok(ProbeResultValue::Single(ProbeValue::Node(version)).into())
             }),
-            ProbeType::Rust => Box::new(|| {
+            ProbeType::Rust => Arc::new(|| {
                 let version = run_command("rustc", &["--version"])?;
-                Ok(ProbeResultValue::Single(ProbeValue::Rust(version)))
+                Ok(ProbeResultValue::Single(ProbeValue::Rust(version)).into())
             }),
         }
     }
@@ -945,6 +1254,7 @@ pub enum ProbeType {
 
     GPUDriver,
     CPUUsage,
+    CpuTemp,
     Disk,
     Battery,
     // TODO: Figure out what this should be
@@ -960,6 +1270,8 @@ pub enum ProbeType {
     Python,
     Node,
     Rust,
+
+    Command,
 }
 
 impl ProbeType {
@@ -992,6 +1304,7 @@ impl ProbeType {
             ProbeType::BIOS => "bios",
             ProbeType::GPUDriver => "gpu_driver",
             ProbeType::CPUUsage => "cpu_usage",
+            ProbeType::CpuTemp => "cpu_temp",
             ProbeType::Disk => "disk",
             ProbeType::Battery => "battery",
             ProbeType::PowerAdapter => "power_adapter",
@@ -1005,6 +1318,7 @@ impl ProbeType {
             ProbeType::Python => "python",
             ProbeType::Node => "node",
             ProbeType::Rust => "rust",
+            ProbeType::Command => "command",
         }
     }
 }
@@ -1014,6 +1328,16 @@ pub enum ProbeResultValue {
     Multiple(Vec<ProbeValue>),
 }
 
+impl ProbeResultValue {
+    /// All values in order.
+    pub fn into_values(self) -> Vec<ProbeValue> {
+        match self {
+            ProbeResultValue::Single(v) => vec![v],
+            ProbeResultValue::Multiple(vs) => vs,
+        }
+    }
+}
+
 impl From<ProbeValue> for ProbeResultValue {
     fn from(value: ProbeValue) -> Self {
         ProbeResultValue::Single(value)
@@ -1026,11 +1350,43 @@ impl From<Vec<ProbeValue>> for ProbeResultValue {
     }
 }
 
-pub type ProbeResult = Result<ProbeResultValue, ProbeError>;
-pub type ProbeResultFunction = Box<dyn Fn() -> ProbeResult + Send + Sync>;
-pub type ProbeList = Vec<(String, ProbeResultFunction)>;
+/// What a successful probe produced: its value, plus the message of any
+/// [`ProbeError::Warning`] its readout raised on the way (e.g. a fallback
+/// source was used).
+pub struct ProbeOutput {
+    pub value: ProbeResultValue,
+    pub warning: Option<String>,
+}
+
+impl From<ProbeResultValue> for ProbeOutput {
+    fn from(value: ProbeResultValue) -> Self {
+        ProbeOutput {
+            value,
+            warning: None,
+        }
+    }
+}
+
+pub type ProbeResult = Result<ProbeOutput, ProbeError>;
+pub type ProbeResultFunction = Arc<dyn Fn() -> ProbeResult + Send + Sync>;
+/// `(label, probe, deadline)`; a `None` deadline lets the probe run to completion.
+pub type ProbeList = Vec<(String, ProbeResultFunction, Option<Duration>)>;
 
 impl ProbeValue {
+    /// How full this value is, `0.0..=1.0`, for the metrics a usage bar can
+    /// show (memory, disk, CPU usage, battery); `None` for the rest.
+    pub fn usage(&self) -> Option<f64> {
+        let ratio = match self {
+            ProbeValue::Memory(used, total) | ProbeValue::Disk(_, _, used, total) => {
+                (*total > 0).then(|| *used as f64 / *total as f64)?
+            }
+            ProbeValue::CPUUsage(percent) => *percent as f64 / 100.0,
+            ProbeValue::Battery(percent) => f64::from(*percent) / 100.0,
+            _ => return None,
+        };
+        Some(ratio.clamp(0.0, 1.0))
+    }
+
     /// Render this probe value to its default display string (option-free).
     ///
     /// This is the baseline rendering shared by every renderer. Option-aware
@@ -1056,7 +1412,7 @@ impl ProbeValue {
                 ShellOptions::default().format(name, version.as_deref())
             }
             ProbeValue::Editor(editor) => editor.to_string(),
-            ProbeValue::Resolution(resolution) => resolution.to_string(),
+            ProbeValue::Resolution(mode, hz) => ResolutionOptions::default().format(mode, *hz),
             ProbeValue::DE(name, version) => DeOptions::default().format(name, version.as_deref()),
             ProbeValue::WM(wm) => wm.to_string(),
             ProbeValue::WMTheme(wm_theme) => wm_theme.to_string(),
@@ -1065,14 +1421,15 @@ impl ProbeValue {
             ProbeValue::Cursor(cursor) => cursor.to_string(),
             ProbeValue::Terminal(terminal) => terminal.to_string(),
             ProbeValue::TerminalFont(terminal_font) => terminal_font.to_string(),
-            ProbeValue::CPU(cpu) => format_cpu(cpu, &CpuOptions::default()),
+            ProbeValue::CPU(cpu, temp) => format_cpu(cpu, *temp, &CpuOptions::default()),
             ProbeValue::GPU(gpu) => GpuOptions::default().format(gpu),
             ProbeValue::Memory(used, total) => MemoryOptions::default().format(*used, *total),
-            ProbeValue::Network(network) => network.to_string(),
-            ProbeValue::Bluetooth(bluetooth) => bluetooth.to_string(),
-            ProbeValue::BIOS(bios) => bios.to_string(),
+            ProbeValue::Network(iface) => NetworkOptions::default().format(iface),
+            ProbeValue::Bluetooth(item) => BluetoothOptions::default().format(item),
+            ProbeValue::BIOS(info) => BiosOptions::default().format(info),
             ProbeValue::GPUDriver(gpu_driver) => gpu_driver.to_string(),
             ProbeValue::CPUUsage(cpu_usage) => format!("{}%", cpu_usage),
+            ProbeValue::CpuTemp(celsius) => TempUnit::default().format(*celsius),
             ProbeValue::Disk(mount, name, used, total) => {
                 DiskOptions::default().format(mount, name, *used, *total)
             }
@@ -1088,6 +1445,8 @@ impl ProbeValue {
             ProbeValue::Node(node) => node.to_string(),
             ProbeValue::Python(python) => python.to_string(),
             ProbeValue::Rust(rust) => rust.to_string(),
+            ProbeValue::Command(line) => line.to_string(),
+            ProbeValue::Text(text) => text.to_string(),
         }
     }
 }
@@ -1109,9 +1468,8 @@ fn read_dmi_model() -> Option<String> {
     // neofetch keys off file existence of the pair, then joins the two contents
     // with a space (either may be empty; `clean_model` trims the result).
     let pair = |a: &str, b: &str| -> Option<String> {
-        let base = "/sys/devices/virtual/dmi/id";
-        let va = read(&format!("{base}/{a}"));
-        let vb = read(&format!("{base}/{b}"));
+        let va = read_dmi(a);
+        let vb = read_dmi(b);
         (va.is_some() || vb.is_some())
             .then(|| format!("{} {}", va.unwrap_or_default(), vb.unwrap_or_default()))
     };
@@ -1121,6 +1479,59 @@ fn read_dmi_model() -> Option<String> {
         .or_else(|| read("/tmp/sysinfo/model"))
 }
 
+/// Read a DMI sysfs field (`board_name`, `bios_vendor`, ...), trimmed, or
+/// `None` when the file doesn't exist.
+#[cfg(target_os = "linux")]
+fn read_dmi(field: &str) -> Option<String> {
+    std::fs::read_to_string(format!("/sys/devices/virtual/dmi/id/{field}"))
+        .ok()
+        .map(|s| s.trim().to_string())
+}
+
+/// EFI global-variable GUID under which firmware publishes `SecureBoot`.
+#[cfg(target_os = "linux")]
+const EFI_GLOBAL_SECURE_BOOT: &str =
+    "/sys/firmware/efi/efivars/SecureBoot-8be4df61-93ca-11d2-aa0d-00e098032b8c";
+
+/// Decode the `SecureBoot` efivar: 4 bytes of attributes, then a single data
+/// byte that is 1 when Secure Boot is enforced.
+#[cfg(any(target_os = "linux", test))]
+fn parse_secure_boot(efivar: &[u8]) -> Option<bool> {
+    efivar.get(4).map(|&b| b == 1)
+}
+
+/// Build the BIOS/firmware probe from DMI sysfs (`bios_vendor`,
+/// `bios_version`, `bios_date`) and `/sys/firmware/efi`. Linux-only.
+#[cfg(target_os = "linux")]
+fn bios_probe_fn() -> ProbeResultFunction {
+    Arc::new(|| {
+        let dmi = |field: &str| read_dmi(field).filter(|s| !s.is_empty());
+        let uefi = std::path::Path::new("/sys/firmware/efi").exists();
+        let info = BiosInfo {
+            vendor: dmi("bios_vendor"),
+            version: dmi("bios_version"),
+            date: dmi("bios_date"),
+            uefi,
+            secure_boot: if uefi {
+                std::fs::read(EFI_GLOBAL_SECURE_BOOT)
+                    .ok()
+                    .and_then(|v| parse_secure_boot(&v))
+            } else {
+                None
+            },
+        };
+        if info.vendor.is_none() && info.version.is_none() && info.date.is_none() {
+            return Err(ProbeError::MetricsUnavailable);
+        }
+        Ok(ProbeResultValue::Single(ProbeValue::BIOS(info)).into())
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+fn bios_probe_fn() -> ProbeResultFunction {
+    Arc::new(|| Err(ProbeError::Unimplemented))
+}
+
 /// libmacchina fallback for the host model (`vendor` + `product`), used when DMI
 /// sysfs is unavailable (e.g. on Windows).
 #[cfg(not(target_os = "macos"))]
@@ -1172,7 +1583,20 @@ fn clean_model(model: &str) -> String {
     s
 }
 
-pub fn format_cpu(model: &str, opts: &CpuOptions) -> String {
+/// Build the CPU probe. The temperature is only read (on the probe thread)
+/// when `temp` asks for it.
+pub fn cpu_probe_fn(opts: CpuOptions) -> ProbeResultFunction {
+    Arc::new(move || {
+        use libmacchina::traits::GeneralReadout as _;
+        let model = general_readout().cpu_model_name()?;
+        let temp = opts.temp.and_then(|_| cpu_temp_celsius());
+        Ok(ProbeResultValue::Single(ProbeValue::CPU(model, temp)).into())
+    })
+}
+
+/// `model` as configured; `temp` is the package temperature in °C, shown when
+/// `opts.temp` is set.
+pub fn format_cpu(model: &str, temp: Option<f64>, opts: &CpuOptions) -> String {
     use libmacchina::traits::GeneralReadout as _;
 
     let mut cpu = clean_cpu_model(model);
@@ -1209,9 +1633,70 @@ pub fn format_cpu(model: &str, opts: &CpuOptions) -> String {
         cpu.push_str(&format!(" @ {}GHz", ghz));
     }
 
+    // `cpu_temp`: neofetch appends the reading in brackets, e.g. "[49.2°C]".
+    if let Some(unit) = opts.temp
+        && let Some(celsius) = temp
+    {
+        cpu.push_str(&format!(" [{}]", unit.format(celsius)));
+    }
+
     cpu
 }
 
+/// hwmon driver names that report the CPU package temperature on `temp1`
+/// (neofetch's list plus `zenpower`).
+#[cfg(any(target_os = "linux", test))]
+fn is_cpu_hwmon(name: &str) -> bool {
+    matches!(
+        name,
+        "coretemp" | "k10temp" | "zenpower" | "cpu_thermal" | "fam15h_power" | "cpu-thermal"
+    )
+}
+
+/// Read the CPU package temperature (°C): the first CPU hwmon sensor's
+/// `temp1_input`, else the `x86_pkg_temp` (or first) thermal zone. Both report
+/// millidegrees. Linux-only.
+#[cfg(target_os = "linux")]
+fn cpu_temp_celsius() -> Option<f64> {
+    let read = |p: PathBuf| {
+        std::fs::read_to_string(p)
+            .ok()
+            .map(|s| s.trim().to_string())
+    };
+    let millis = |p: PathBuf| read(p)?.parse::<i64>().ok().map(|m| m as f64 / 1000.0);
+
+    let mut hwmons: Vec<PathBuf> = std::fs::read_dir("/sys/class/hwmon")
+        .map(|rd| rd.flatten().map(|e| e.path()).collect())
+        .unwrap_or_default();
+    hwmons.sort();
+    for dir in hwmons {
+        if read(dir.join("name")).is_some_and(|n| is_cpu_hwmon(&n))
+            && let Some(c) = millis(dir.join("temp1_input"))
+        {
+            return Some(c);
+        }
+    }
+
+    let mut zones: Vec<PathBuf> = std::fs::read_dir("/sys/class/thermal")
+        .map(|rd| {
+            rd.flatten()
+                .map(|e| e.path())
+                .filter(|p| p.join("temp").exists())
+                .collect()
+        })
+        .unwrap_or_default();
+    zones.sort();
+    let pkg = zones
+        .iter()
+        .find(|z| read(z.join("type")).as_deref() == Some("x86_pkg_temp"));
+    pkg.or(zones.first()).and_then(|z| millis(z.join("temp")))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn cpu_temp_celsius() -> Option<f64> {
+    None
+}
+
 /// Strip the marketing cruft neofetch removes from CPU model strings, mirroring
 /// the exact removal sequence in neofetch's `get_cpu` (lines `cpu="${cpu//…}"`).
 ///
@@ -1373,9 +1858,150 @@ fn de_version(de: &str) -> Option<String> {
         .filter(|s| !s.is_empty())
 }
 
+/// Build the resolution probe. With `refresh_rate` on (Linux), report each
+/// connected display as its own `WxH` + Hz line, taken from the compositor
+/// (`xrandr` on X11, `wlr-randr` on wlroots Wayland) and falling back to DRM
+/// sysfs; otherwise libmacchina's resolution string.
+pub fn resolution_probe_fn(opts: ResolutionOptions) -> ProbeResultFunction {
+    Arc::new(move || {
+        use libmacchina::traits::GeneralReadout as _;
+        if opts.refresh_rate
+            && let Some(displays) = display_modes()
+        {
+            return Ok(ProbeResultValue::Multiple(
+                displays
+                    .into_iter()
+                    .map(|(mode, hz)| ProbeValue::Resolution(mode, hz))
+                    .collect(),
+            )
+            .into());
+        }
+
+        let raw = general_readout().resolution()?;
+        // macOS: neofetch shows only the logical/scaled resolution
+        // (e.g. "1728x1117"), no native-mode or refresh-rate suffix.
+        #[cfg(target_os = "macos")]
+        let value = scaled_resolution(&raw);
+        #[cfg(not(target_os = "macos"))]
+        let value = raw;
+        Ok(ProbeResultValue::Single(ProbeValue::Resolution(value, None)).into())
+    })
+}
+
+/// Current mode of every connected display, from the running compositor when
+/// it can be queried (`xrandr`, or `wlr-randr` on wlroots; GNOME and KDE
+/// Wayland sessions have neither), else the DRM connectors' preferred modes.
+/// `None` when no source reports a display.
+#[cfg(target_os = "linux")]
+fn display_modes() -> Option<Vec<(String, Option<f64>)>> {
+    let wayland = std::env::var_os("WAYLAND_DISPLAY").is_some();
+    let from_compositor = if wayland {
+        run_command("wlr-randr", &[])
+            .ok()
+            .map(|out| parse_wlr_randr(&out))
+    } else if std::env::var_os("DISPLAY").is_some() {
+        run_command("xrandr", &["--nograb", "--current"])
+            .ok()
+            .map(|out| parse_xrandr(&out))
+    } else {
+        None
+    };
+    from_compositor
+        .filter(|d| !d.is_empty())
+        .or_else(|| Some(drm_display_modes()).filter(|d| !d.is_empty()))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn display_modes() -> Option<Vec<(String, Option<f64>)>> {
+    None
+}
+
+/// Preferred mode of each connected DRM connector (`/sys/class/drm/card*-*`):
+/// the first line of `modes`, with the refresh rate from the EDID's first
+/// detailed timing when it describes that same mode.
+#[cfg(target_os = "linux")]
+fn drm_display_modes() -> Vec<(String, Option<f64>)> {
+    let mut connectors: Vec<PathBuf> = std::fs::read_dir("/sys/class/drm")
+        .map(|rd| {
+            rd.flatten()
+                .map(|e| e.path())
+                .filter(|p| p.join("status").exists())
+                .collect()
+        })
+        .unwrap_or_default();
+    connectors.sort();
+    connectors
+        .iter()
+        .filter(|c| {
+            std::fs::read_to_string(c.join("status")).is_ok_and(|s| s.trim() == "connected")
+        })
+        .filter_map(|c| {
+            let modes = std::fs::read_to_string(c.join("modes")).ok()?;
+            let mode = modes.lines().next()?.trim().to_string();
+            let hz = std::fs::read(c.join("edid"))
+                .ok()
+                .and_then(|edid| parse_edid_timing(&edid))
+                .filter(|(m, _)| *m == mode)
+                .map(|(_, hz)| hz);
+            Some((mode, hz))
+        })
+        .collect()
+}
+
+/// Decode the first detailed timing descriptor of an EDID block (bytes
+/// 54..72) into its `WxH` mode and refresh rate: pixel clock / (h total ×
+/// v total).
+#[cfg(any(target_os = "linux", test))]
+fn parse_edid_timing(edid: &[u8]) -> Option<(String, f64)> {
+    let d = edid.get(54..72)?;
+    let clock_hz = u16::from_le_bytes([d[0], d[1]]) as f64 * 10_000.0;
+    if clock_hz == 0.0 {
+        return None;
+    }
+    let h_active = d[2] as u32 | ((d[4] as u32 >> 4) << 8);
+    let h_blank = d[3] as u32 | ((d[4] as u32 & 0xf) << 8);
+    let v_active = d[5] as u32 | ((d[7] as u32 >> 4) << 8);
+    let v_blank = d[6] as u32 | ((d[7] as u32 & 0xf) << 8);
+    let total = ((h_active + h_blank) * (v_active + v_blank)) as f64;
+    (total > 0.0).then(|| (format!("{h_active}x{v_active}"), clock_hz / total))
+}
+
+/// Current modes from `xrandr --current`: each mode line marked `*` yields its
+/// `WxH` and the starred rate (e.g. "   2560x1440  165.00*+  144.00").
+#[cfg(any(target_os = "linux", test))]
+fn parse_xrandr(output: &str) -> Vec<(String, Option<f64>)> {
+    output
+        .lines()
+        .filter(|l| l.starts_with(' ') && l.contains('*'))
+        .filter_map(|l| {
+            let mut tokens = l.split_whitespace();
+            let mode = tokens.next()?.to_string();
+            let hz = tokens
+                .find(|t| t.contains('*'))
+                .and_then(|t| t.trim_end_matches(['*', '+']).parse().ok());
+            Some((mode, hz))
+        })
+        .collect()
+}
+
+/// Current modes from `wlr-randr`: each mode line tagged `current`, e.g.
+/// "    2560x1440 px, 165.000000 Hz (preferred, current)".
+#[cfg(any(target_os = "linux", test))]
+fn parse_wlr_randr(output: &str) -> Vec<(String, Option<f64>)> {
+    output
+        .lines()
+        .filter(|l| l.contains(" px, ") && l.contains("current"))
+        .filter_map(|l| {
+            let (mode, rest) = l.trim().split_once(" px, ")?;
+            let hz = rest.split_whitespace().next().and_then(|t| t.parse().ok());
+            Some((mode.to_string(), hz))
+        })
+        .collect()
+}
+
 /// Build the disk probe, filtering mount points by `opts.show` (empty = all).
 pub fn disk_probe_fn(opts: DiskOptions) -> ProbeResultFunction {
-    Box::new(move || {
+    Arc::new(move || {
         let _span = debug_span!("disk_scan").entered();
         let disks = Disks::new_with_refreshed_list();
         let entries: Vec<ProbeValue> = disks
@@ -1396,13 +2022,13 @@ pub fn disk_probe_fn(opts: DiskOptions) -> ProbeResultFunction {
                 )
             })
             .collect();
-        Ok(ProbeResultValue::Multiple(entries))
+        Ok(ProbeResultValue::Multiple(entries).into())
     })
 }
 
 /// Build the GPU probe, filtering by `opts.gpu_type` (all/dedicated/integrated).
 pub fn gpu_probe_fn(opts: GpuOptions) -> ProbeResultFunction {
-    Box::new(move || {
+    Arc::new(move || {
         let _span = debug_span!("gpu_readout").entered();
         let entries: Vec<ProbeValue> = gpus_list()?
             .into_iter()
@@ -1413,7 +2039,7 @@ pub fn gpu_probe_fn(opts: GpuOptions) -> ProbeResultFunction {
             })
             .map(ProbeValue::GPU)
             .collect();
-        Ok(ProbeResultValue::Multiple(entries))
+        Ok(ProbeResultValue::Multiple(entries).into())
     })
 }
 
@@ -1563,7 +2189,7 @@ mod song {
 /// `song_format`; `song_shorthand` drops the album. MPRIS is Linux-only.
 #[cfg(target_os = "linux")]
 pub fn song_probe_fn(opts: SongOptions) -> ProbeResultFunction {
-    Box::new(move || {
+    Arc::new(move || {
         let info = song::current(&opts.player).ok_or(ProbeError::MetricsUnavailable)?;
         let fmt = if opts.shorthand {
             "%artist% - %title%"
@@ -1583,19 +2209,322 @@ pub fn song_probe_fn(opts: SongOptions) -> ProbeResultFunction {
         if text.trim().is_empty() {
             return Err(ProbeError::MetricsUnavailable);
         }
-        Ok(ProbeResultValue::Single(ProbeValue::Song(text)))
+        Ok(ProbeResultValue::Single(ProbeValue::Song(text)).into())
     })
 }
 
 #[cfg(not(target_os = "linux"))]
 pub fn song_probe_fn(_opts: SongOptions) -> ProbeResultFunction {
-    Box::new(|| Err(ProbeError::Unimplemented))
+    Arc::new(|| Err(ProbeError::Unimplemented))
+}
+
+/// Build a user-defined command probe. The command runs on the probe thread
+/// via [`run_command`], so the probe deadline kills it if it hangs.
+pub fn command_probe_fn(opts: CommandOptions) -> ProbeResultFunction {
+    Arc::new(move || {
+        if opts.command.trim().is_empty() {
+            return Err(ProbeError::Other("no command configured".to_string()));
+        }
+        let args: Vec<&str> = opts.args.iter().map(String::as_str).collect();
+        let output = if opts.shell {
+            // `sh -c <command> purr <args…>`: args become `$1`, `$2`, ….
+            #[cfg(not(windows))]
+            let (shell, mut shell_args) = ("sh", vec!["-c", opts.command.as_str(), "purr"]);
+            #[cfg(windows)]
+            let (shell, mut shell_args) = ("cmd", vec!["/C", opts.command.as_str()]);
+            shell_args.extend(args);
+            run_command(shell, &shell_args)?
+        } else {
+            run_command(&opts.command, &args)?
+        };
+        let mut lines = command_lines(&output, opts.multiline).map(ProbeValue::Command);
+        if opts.multiline {
+            Ok(ProbeResultValue::Multiple(lines.collect()).into())
+        } else {
+            lines
+                .next()
+                .map(|line| ProbeResultValue::Single(line).into())
+                .ok_or(ProbeError::MetricsUnavailable)
+        }
+    })
+}
+
+/// Non-blank, trimmed lines of command output; just the first unless `multiline`.
+fn command_lines(output: &str, multiline: bool) -> impl Iterator<Item = String> + '_ {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .take(if multiline { usize::MAX } else { 1 })
+        .map(str::to_string)
+}
+
+/// Adapter and device lookup over BlueZ (system D-Bus), pure-Rust via zbus.
+#[cfg(target_os = "linux")]
+mod bluetooth {
+    use std::collections::HashMap;
+
+    use zbus::blocking::{Connection, fdo::ObjectManagerProxy};
+    use zbus::zvariant::OwnedValue;
+
+    use super::BluetoothItem;
+
+    type Props = HashMap<String, OwnedValue>;
+
+    fn prop<T: TryFrom<OwnedValue>>(props: &Props, key: &str) -> Option<T> {
+        props.get(key).and_then(|v| T::try_from(v.clone()).ok())
+    }
+
+    /// Enumerate BlueZ objects via `GetManagedObjects`, returning every adapter
+    /// (`adapters`) or every connected device, in object-path order.
+    pub fn query(adapters: bool) -> Option<Vec<BluetoothItem>> {
+        let conn = Connection::system().ok()?;
+        let manager = ObjectManagerProxy::builder(&conn)
+            .destination("org.bluez")
+            .and_then(|b| b.path("/"))
+            .and_then(|b| b.build())
+            .ok()?;
+        let objects = manager.get_managed_objects().ok()?;
+
+        let mut paths: Vec<_> = objects.keys().collect();
+        paths.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+
+        let mut items = Vec::new();
+        for path in paths {
+            let ifaces = &objects[path];
+            let iface = |name: &str| {
+                ifaces
+                    .iter()
+                    .find(|(k, _)| k.as_str() == name)
+                    .map(|(_, v)| v)
+            };
+            if adapters {
+                if let Some(a) = iface("org.bluez.Adapter1") {
+                    let id = path.as_str().rsplit('/').next().unwrap_or_default();
+                    items.push(BluetoothItem::Adapter {
+                        id: id.to_string(),
+                        name: prop(a, "Alias")
+                            .or_else(|| prop(a, "Name"))
+                            .unwrap_or_else(|| id.to_string()),
+                        powered: prop(a, "Powered").unwrap_or(false),
+                    });
+                }
+            } else if let Some(d) = iface("org.bluez.Device1")
+                && prop::<bool>(d, "Connected") == Some(true)
+            {
+                items.push(BluetoothItem::Device {
+                    name: prop(d, "Alias")
+                        .or_else(|| prop(d, "Name"))
+                        .or_else(|| prop(d, "Address"))
+                        .unwrap_or_default(),
+                    battery: iface("org.bluez.Battery1").and_then(|b| prop(b, "Percentage")),
+                });
+            }
+        }
+        Some(items)
+    }
+}
+
+/// Build the Bluetooth probe: one line per adapter or per connected device,
+/// per `opts.mode`. BlueZ is Linux-only.
+#[cfg(target_os = "linux")]
+pub fn bluetooth_probe_fn(opts: BluetoothOptions) -> ProbeResultFunction {
+    Arc::new(move || {
+        let _span = debug_span!("bluez_query").entered();
+        let items = bluetooth::query(opts.mode == crate::config::BluetoothMode::Adapters)
+            .ok_or(ProbeError::MetricsUnavailable)?;
+        Ok(
+            ProbeResultValue::Multiple(items.into_iter().map(ProbeValue::Bluetooth).collect())
+                .into(),
+        )
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn bluetooth_probe_fn(_opts: BluetoothOptions) -> ProbeResultFunction {
+    Arc::new(|| Err(ProbeError::Unimplemented))
+}
+
+/// Match `name` against a shell-style glob supporting `*` (any run) and `?`
+/// (any single character). A pattern without wildcards is an exact match.
+pub fn glob_match(pattern: &str, name: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let n: Vec<char> = name.chars().collect();
+    let (mut pi, mut ni) = (0, 0);
+    // Position of the last `*` seen and the name index it was matched against,
+    // so a mismatch can backtrack by letting that `*` swallow one more char.
+    let mut star: Option<(usize, usize)> = None;
+    while ni < n.len() {
+        match p.get(pi) {
+            Some('*') => {
+                star = Some((pi, ni));
+                pi += 1;
+            }
+            Some(&c) if c == '?' || c == n[ni] => {
+                pi += 1;
+                ni += 1;
+            }
+            _ => match star {
+                Some((sp, sn)) => {
+                    pi = sp + 1;
+                    ni = sn + 1;
+                    star = Some((sp, sn + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    p[pi..].iter().all(|&c| c == '*')
+}
+
+/// Whether an interface counts as active: `operstate` "up", or "unknown" (as
+/// reported by loopback and many tun/wireguard links) with `IFF_UP` set in its
+/// hex `flags`.
+#[cfg(any(target_os = "linux", test))]
+fn net_is_active(operstate: &str, flags: Option<&str>) -> bool {
+    const IFF_UP: u32 = 0x1;
+    match operstate {
+        "up" => true,
+        "unknown" => flags
+            .and_then(|f| u32::from_str_radix(f.trim_start_matches("0x"), 16).ok())
+            .is_some_and(|f| f & IFF_UP != 0),
+        _ => false,
+    }
+}
+
+/// Build the network probe: one line per interface under `/sys/class/net`
+/// that passes `opts` (name/glob filter, loopback/virtual/down hiding).
+#[cfg(target_os = "linux")]
+pub fn network_probe_fn(opts: NetworkOptions) -> ProbeResultFunction {
+    Arc::new(move || {
+        let _span = debug_span!("net_scan").entered();
+        let mut paths: Vec<PathBuf> = std::fs::read_dir("/sys/class/net")
+            .map_err(|_| ProbeError::MetricsUnavailable)?
+            .flatten()
+            .map(|e| e.path())
+            .collect();
+        paths.sort();
+
+        let mut entries = Vec::new();
+        for path in paths {
+            let Some(name) = path.file_name().map(|n| n.to_string_lossy().to_string()) else {
+                continue;
+            };
+            if !opts.shows(&name) {
+                continue;
+            }
+            let read = |file: &str| {
+                std::fs::read_to_string(path.join(file))
+                    .ok()
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+            };
+            // ARPHRD_LOOPBACK; physical NICs also have a backing `device` link,
+            // which bridges, veth pairs, tun/tap and friends lack.
+            let loopback = read("type").as_deref() == Some("772");
+            let is_virtual = !loopback && !path.join("device").exists();
+            if (loopback && !opts.loopback) || (is_virtual && !opts.virtual_interfaces) {
+                continue;
+            }
+            let state = read("operstate").unwrap_or_else(|| "unknown".to_string());
+            if !opts.down && !net_is_active(&state, read("flags").as_deref()) {
+                continue;
+            }
+            entries.push(ProbeValue::Network(NetInterface {
+                mac: read("address").filter(|a| a != "00:00:00:00:00:00"),
+                // `speed` is -1 (or unreadable) when the link is down or unknown.
+                speed_mbps: read("speed")
+                    .and_then(|s| s.parse::<i64>().ok())
+                    .filter(|&s| s > 0)
+                    .map(|s| s as u32),
+                mtu: read("mtu").and_then(|s| s.parse().ok()),
+                wireless: path.join("wireless").exists() || path.join("phy80211").exists(),
+                loopback,
+                name,
+                state,
+            }));
+        }
+        Ok(ProbeResultValue::Multiple(entries).into())
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn network_probe_fn(_opts: NetworkOptions) -> ProbeResultFunction {
+    Arc::new(|| Err(ProbeError::Unimplemented))
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{clean_cpu_model, clean_model, format_cpu, normalize_wm, with_gtk_tag};
-    use crate::config::{CoresMode, CpuOptions};
+    use super::{
+        clean_cpu_model, clean_model, format_cpu, format_cursor, glob_match, is_cpu_hwmon,
+        map_accent, map_terminal, net_is_active, normalize_wm, parse_chipset_models,
+        parse_edid_timing, parse_port_count, parse_secure_boot, parse_vm_stat_page_size,
+        parse_vm_stat_pages, parse_wlr_randr, parse_xrandr, parse_xresource, scaled_resolution,
+        with_gtk_tag,
+    };
+    use crate::config::{CoresMode, CpuOptions, TempUnit};
+
+    #[test]
+    fn command_output_lines() {
+        use super::command_lines;
+
+        let out = "  build 42 \n\n context: prod\n";
+        assert_eq!(command_lines(out, false).collect::<Vec<_>>(), ["build 42"]);
+        assert_eq!(
+            command_lines(out, true).collect::<Vec<_>>(),
+            ["build 42", "context: prod"]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn command_probe_runs_through_shell() {
+        use super::{ProbeOutput, ProbeResultValue, ProbeValue, command_probe_fn};
+        use crate::config::CommandOptions;
+
+        let opts = CommandOptions {
+            command: "echo \"$1\"; echo two".to_string(),
+            args: vec!["one".to_string()],
+            shell: true,
+            multiline: true,
+            ..Default::default()
+        };
+        let Ok(ProbeOutput {
+            value: ProbeResultValue::Multiple(values),
+            ..
+        }) = command_probe_fn(opts)()
+        else {
+            panic!("expected multiple lines");
+        };
+        let lines: Vec<String> = values.iter().map(ProbeValue::format).collect();
+        assert_eq!(lines, ["one", "two"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn run_command_kills_child_at_deadline() {
+        use std::time::{Duration, Instant};
+
+        use super::{ProbeError, run_command, with_deadline};
+
+        let start = Instant::now();
+        let deadline = Some(start + Duration::from_millis(100));
+        let result = with_deadline(deadline, || run_command("sleep", &["30"]));
+        assert!(matches!(result, Err(ProbeError::TimedOut)));
+        assert!(start.elapsed() < Duration::from_secs(5));
+        // Without a deadline, output is collected as before.
+        assert_eq!(run_command("echo", &["hi"]).ok().as_deref(), Some("hi"));
+        let under_deadline = with_deadline(Some(Instant::now() + Duration::from_secs(5)), || {
+            run_command("echo", &["hi"])
+        });
+        assert_eq!(under_deadline.ok().as_deref(), Some("hi"));
+        // A panicking probe doesn't leave its deadline on the thread.
+        let panicked = std::panic::catch_unwind(|| {
+            with_deadline(Some(Instant::now()), || panic!("probe bug"));
+        });
+        assert!(panicked.is_err());
+        assert_eq!(super::PROBE_DEADLINE.get(), None);
+    }
 
     #[test]
     fn wm_renames_match_neofetch() {
@@ -1615,6 +2544,31 @@ mod tests {
         assert_eq!(with_gtk_tag("Arc-Dark", false), "Arc-Dark [GTK3]");
     }
 
+    #[test]
+    fn cursor_size_and_tag() {
+        assert_eq!(
+            format_cursor("Adwaita", Some("24"), Some(true)),
+            "Adwaita (24px) [GTK2/3]"
+        );
+        assert_eq!(
+            format_cursor("breeze_cursors", Some("0"), None),
+            "breeze_cursors"
+        );
+        assert_eq!(format_cursor("Bibata", None, Some(false)), "Bibata [GTK3]");
+    }
+
+    #[test]
+    fn xresources_lookup() {
+        let xres =
+            "! comment: ignored\nXft.dpi: 96\n*Xcursor.theme:  Bibata-Modern\nXcursor.size: 32\n";
+        assert_eq!(
+            parse_xresource(xres, "Xcursor.theme").as_deref(),
+            Some("Bibata-Modern")
+        );
+        assert_eq!(parse_xresource(xres, "Xcursor.size").as_deref(), Some("32"));
+        assert_eq!(parse_xresource(xres, "Xcursor.missing"), None);
+    }
+
     #[test]
     fn model_dmi_and_oem_cleanup() {
         // Real motherboard string passes through (whitespace collapsed).
@@ -1646,7 +2600,28 @@ mod tests {
             speed: false,
             ..Default::default()
         };
-        assert_eq!(format_cpu("AMD Ryzen 7 7800X3D", &opts), "Ryzen 7 7800X3D");
+        assert_eq!(
+            format_cpu("AMD Ryzen 7 7800X3D", None, &opts),
+            "Ryzen 7 7800X3D"
+        );
+    }
+
+    #[test]
+    fn cpu_temp_formats_the_gathered_reading() {
+        let opts = CpuOptions {
+            cores: CoresMode::Off,
+            speed: false,
+            temp: Some(TempUnit::C),
+            ..Default::default()
+        };
+        assert_eq!(
+            format_cpu("AMD Ryzen 7 7800X3D", Some(49.2), &opts),
+            "AMD Ryzen 7 7800X3D [49.2°C]"
+        );
+        assert_eq!(
+            format_cpu("AMD Ryzen 7 7800X3D", None, &opts),
+            "AMD Ryzen 7 7800X3D"
+        );
     }
 
     #[test]
@@ -1657,7 +2632,7 @@ mod tests {
             ..Default::default()
         };
         assert_eq!(
-            format_cpu("AMD Ryzen 7 7800X3D", &opts),
+            format_cpu("AMD Ryzen 7 7800X3D", None, &opts),
             "AMD Ryzen 7 7800X3D"
         );
     }
@@ -1720,11 +2695,6 @@ mod tests {
         );
     }
 
-    use super::{
-        map_accent, map_terminal, parse_chipset_models, parse_port_count, parse_vm_stat_page_size,
-        parse_vm_stat_pages, scaled_resolution,
-    };
-
     #[test]
     fn resolution_reduces_to_scaled() {
         assert_eq!(
@@ -1787,4 +2757,74 @@ mod tests {
         let out = "The following ports are currently installed:\n  pkg-a @1\n  pkg-b @2\n";
         assert_eq!(parse_port_count(out), 3);
     }
+
+    #[test]
+    fn edid_detailed_timing_refresh() {
+        // 1920x1080 @ 148.5 MHz, 2200x1125 total -> 60 Hz.
+        let mut edid = vec![0u8; 128];
+        edid[54..62].copy_from_slice(&[0x02, 0x3a, 0x80, 0x18, 0x71, 0x38, 0x2d, 0x40]);
+        let (mode, hz) = parse_edid_timing(&edid).expect("timing");
+        assert_eq!(mode, "1920x1080");
+        assert!((hz - 60.0).abs() < 0.01);
+        // Zero pixel clock = not a timing descriptor.
+        assert!(parse_edid_timing(&[0u8; 128]).is_none());
+    }
+
+    #[test]
+    fn xrandr_and_wlr_randr_current_modes() {
+        let xrandr = "Screen 0: minimum 320 x 200, current 4480 x 1440\n\
+DP-1 connected primary 2560x1440+0+0\n   2560x1440    165.00*+ 144.00\n   1920x1080     60.00\n\
+HDMI-1 connected 1920x1080+2560+0\n   1920x1080     60.00 +  59.94*\n";
+        assert_eq!(
+            parse_xrandr(xrandr),
+            vec![
+                ("2560x1440".to_string(), Some(165.0)),
+                ("1920x1080".to_string(), Some(59.94)),
+            ]
+        );
+        let wlr = "DP-1 \"Dell Inc. DELL S2721DGF\"\n  Modes:\n    2560x1440 px, 165.000000 Hz (preferred, current)\n    1920x1080 px, 60.000000 Hz\n";
+        assert_eq!(
+            parse_wlr_randr(wlr),
+            vec![("2560x1440".to_string(), Some(165.0))]
+        );
+    }
+
+    #[test]
+    fn cpu_hwmon_drivers() {
+        assert!(is_cpu_hwmon("k10temp"));
+        assert!(is_cpu_hwmon("coretemp"));
+        assert!(is_cpu_hwmon("zenpower"));
+        assert!(!is_cpu_hwmon("nvme"));
+        assert!(!is_cpu_hwmon("amdgpu"));
+    }
+
+    #[test]
+    fn secure_boot_efivar_data_byte() {
+        // Attributes (BS|RT = 0x06, little-endian) followed by the data byte.
+        assert_eq!(parse_secure_boot(&[0x06, 0, 0, 0, 1]), Some(true));
+        assert_eq!(parse_secure_boot(&[0x06, 0, 0, 0, 0]), Some(false));
+        assert_eq!(parse_secure_boot(&[0x06, 0, 0, 0]), None);
+    }
+
+    #[test]
+    fn glob_matches_interface_names() {
+        assert!(glob_match("eth0", "eth0"));
+        assert!(!glob_match("eth0", "eth01"));
+        assert!(glob_match("en*", "enp5s0"));
+        assert!(glob_match("wl?0", "wlp0"));
+        assert!(glob_match("*s0", "enp5s0"));
+        assert!(glob_match("e*p*0", "enp5s0"));
+        assert!(!glob_match("wl*", "enp5s0"));
+        assert!(glob_match("*", ""));
+    }
+
+    #[test]
+    fn net_active_from_operstate_and_flags() {
+        assert!(net_is_active("up", None));
+        assert!(!net_is_active("down", Some("0x1003")));
+        // Loopback / tun report "unknown"; fall back to IFF_UP.
+        assert!(net_is_active("unknown", Some("0x9")));
+        assert!(!net_is_active("unknown", Some("0x1002")));
+        assert!(!net_is_active("unknown", None));
+    }
 }
@@ -1,12 +1,22 @@
-//! Kitty graphics protocol image output.
+//! Terminal image output: the Kitty graphics protocol, iTerm2 inline images,
+//! DEC sixel, and `▀`/`▄` half blocks for any colour terminal.
 //!
-//! Self-contained: PNG bytes are base64-encoded and transmitted in chunked APC
-//! escapes. No image-decoding dependency — only PNG sources are supported, and
-//! their dimensions are read straight from the IHDR chunk.
+//! Sources (PNG, JPEG, GIF's first frame, WebP) are decoded and scaled to the
+//! cell box they are shown in, so a large wallpaper costs no more than an
+//! icon. Kitty and iTerm2 are then sent that as PNG, base64-encoded in APC or
+//! OSC 1337 escapes; sixel quantizes it to a 256-colour palette and encodes
+//! six pixel rows at a time; half blocks draw two pixels per cell as its
+//! foreground and background colours.
 
-use std::io::{IsTerminal, Write};
+use std::{
+    collections::BTreeMap,
+    fmt::Write as _,
+    io::{Cursor, IsTerminal, Write},
+};
 
 use base64::Engine;
+use color_quant::NeuQuant;
+use image::{ImageFormat, RgbaImage, imageops::FilterType};
 
 /// Whether the terminal is a Kitty-graphics-capable TTY.
 pub fn kitty_supported() -> bool {
@@ -21,16 +31,123 @@ pub fn kitty_supported() -> bool {
         .unwrap_or(false)
 }
 
-/// Read a PNG's pixel dimensions from its IHDR chunk, or `None` if `data` is
-/// not a PNG.
-pub fn png_dimensions(data: &[u8]) -> Option<(u32, u32)> {
-    // 8-byte signature, then IHDR: length(4) + "IHDR"(4) + width(4) + height(4).
-    if data.len() < 24 || &data[1..4] != b"PNG" || &data[12..16] != b"IHDR" {
-        return None;
+/// Whether the terminal is a TTY that speaks iTerm2's inline image protocol:
+/// iTerm2 itself (also over tmux/ssh via `LC_TERMINAL`), WezTerm or Konsole.
+pub fn iterm2_supported() -> bool {
+    if !std::io::stdout().is_terminal() {
+        return false;
+    }
+    let var = |name| std::env::var(name).unwrap_or_default();
+    matches!(var("TERM_PROGRAM").as_str(), "iTerm.app" | "WezTerm")
+        || var("LC_TERMINAL") == "iTerm2"
+        || std::env::var_os("KONSOLE_VERSION").is_some()
+}
+
+/// Whether stdout is a TTY that draws sixel graphics. Most sixel terminals
+/// present themselves as `xterm`, so the terminal is asked for its primary
+/// device attributes (DA1) and must list attribute 4; only a terminal that
+/// doesn't answer is judged by its `TERM`/`TERM_PROGRAM`.
+pub fn sixel_supported() -> bool {
+    if !std::io::stdout().is_terminal() {
+        return false;
+    }
+    match query_device_attributes() {
+        Some(attrs) => attrs.contains(&4),
+        None => {
+            let var = |name| std::env::var(name).unwrap_or_default();
+            let term = var("TERM");
+            ["foot", "mlterm", "contour", "yaft"]
+                .iter()
+                .any(|t| term.contains(t))
+                || matches!(var("TERM_PROGRAM").as_str(), "WezTerm" | "mintty")
+                || std::env::var_os("KONSOLE_VERSION").is_some()
+        }
+    }
+}
+
+/// Ask the controlling terminal for its primary device attributes
+/// (`CSI c`), waiting up to 200 ms for the `CSI ? … c` reply. `None` when
+/// there is no terminal to ask or it doesn't answer in time.
+#[cfg(unix)]
+fn query_device_attributes() -> Option<Vec<u16>> {
+    use std::{fs::OpenOptions, io::Read, sync::mpsc, time::Duration};
+
+    let mut tty = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")
+        .ok()?;
+    let mut reader = tty.try_clone().ok()?;
+    crossterm::terminal::enable_raw_mode().ok()?;
+    let (tx, rx) = mpsc::channel();
+    // A terminal that never answers leaves this thread blocked on the read;
+    // it is detached and goes away with the process.
+    std::thread::spawn(move || {
+        let mut reply = Vec::new();
+        let mut byte = [0u8];
+        while reader.read_exact(&mut byte).is_ok() {
+            reply.push(byte[0]);
+            if let Some(attrs) = parse_device_attributes(&reply) {
+                let _ = tx.send(attrs);
+                return;
+            }
+        }
+    });
+    let attrs = tty
+        .write_all(b"\x1b[c")
+        .and_then(|()| tty.flush())
+        .ok()
+        .and_then(|()| rx.recv_timeout(Duration::from_millis(200)).ok());
+    let _ = crossterm::terminal::disable_raw_mode();
+    attrs
+}
+
+#[cfg(not(unix))]
+fn query_device_attributes() -> Option<Vec<u16>> {
+    None
+}
+
+/// The attributes in a complete DA1 reply (`ESC [ ? 62 ; 4 ; 22 c`) at the end
+/// of `reply`, or `None` until one has arrived.
+fn parse_device_attributes(reply: &[u8]) -> Option<Vec<u16>> {
+    let reply = reply.strip_suffix(b"c")?;
+    let start = reply.windows(3).rposition(|w| w == b"\x1b[?")? + 3;
+    let params = std::str::from_utf8(&reply[start..]).ok()?;
+    params.split(';').map(|p| p.parse().ok()).collect()
+}
+
+/// Pixel size of one terminal cell as the terminal reports it, or a typical
+/// 10x20 when it reports none.
+pub fn cell_size() -> (u32, u32) {
+    match crossterm::terminal::window_size() {
+        Ok(s) if s.width > 0 && s.height > 0 && s.columns > 0 && s.rows > 0 => {
+            (u32::from(s.width / s.columns), u32::from(s.height / s.rows))
+        }
+        _ => (10, 20),
     }
-    let w = u32::from_be_bytes(data[16..20].try_into().ok()?);
-    let h = u32::from_be_bytes(data[20..24].try_into().ok()?);
-    Some((w, h))
+}
+
+/// Decode a PNG, JPEG, GIF (first frame) or WebP image to RGBA pixels, or
+/// `None` if it isn't one of those or can't be read.
+pub fn decode(data: &[u8]) -> Option<RgbaImage> {
+    image::load_from_memory(data).ok().map(|img| img.to_rgba8())
+}
+
+/// Scale `img` to `cols` cells of `cell` pixels wide, keeping its aspect
+/// ratio: the pixels to show and the rows of cells they cover.
+pub fn fit(img: &RgbaImage, cols: u32, (cell_w, cell_h): (u32, u32)) -> (RgbaImage, u32) {
+    let width = cols.max(1) * cell_w.max(1);
+    let height = (f64::from(width) * f64::from(img.height()) / f64::from(img.width())).round();
+    let height = (height as u32).max(1);
+    let scaled = image::imageops::resize(img, width, height, FilterType::Triangle);
+    (scaled, height.div_ceil(cell_h.max(1)))
+}
+
+/// `img` as PNG, for the protocols that take one.
+pub fn encode_png(img: &RgbaImage) -> Option<Vec<u8>> {
+    let mut png = Cursor::new(Vec::new());
+    img.write_to(&mut png, ImageFormat::Png).ok()?;
+    Some(png.into_inner())
 }
 
 /// Transmit and display `png` via the Kitty graphics protocol, sized to
@@ -53,23 +170,236 @@ pub fn display_png(w: &mut impl Write, png: &[u8], cols: u32, rows: u32) -> std:
     Ok(())
 }
 
+/// Display `png` with iTerm2's `OSC 1337 ; File=` sequence, fitted into
+/// `cols`x`rows` terminal cells at the current cursor position.
+pub fn display_iterm2(w: &mut impl Write, png: &[u8], cols: u32, rows: u32) -> std::io::Result<()> {
+    let b64 = base64::engine::general_purpose::STANDARD.encode(png);
+    write!(
+        w,
+        "\x1b]1337;File=inline=1;size={};width={cols};height={rows};preserveAspectRatio=1:{b64}\x07",
+        png.len()
+    )
+}
+
+/// Display `img` as sixel, pixel for pixel, at the current cursor position.
+pub fn display_sixel(w: &mut impl Write, img: &RgbaImage) -> std::io::Result<()> {
+    w.write_all(encode_sixel(img).as_bytes())
+}
+
+/// Encode `img` as a DEC sixel image: quantized to at most 256 colours, then
+/// per six-row band one run-length encoded line per colour in it. Pixels under
+/// half opacity are left transparent.
+fn encode_sixel(img: &RgbaImage) -> String {
+    let (width, height) = img.dimensions();
+    let quant = NeuQuant::new(10, 256, img.as_raw());
+    let index: Vec<Option<usize>> = img
+        .pixels()
+        .map(|p| (p[3] >= 128).then(|| quant.index_of(&p.0)))
+        .collect();
+
+    // `P2 = 1`: pixels left at 0 keep the background.
+    let mut out = format!("\x1bP0;1;0q\"1;1;{width};{height}");
+    let mut used = vec![false; 256];
+    for &i in index.iter().flatten() {
+        used[i] = true;
+    }
+    let percent = |c: u8| u32::from(c) * 100 / 255;
+    for (i, rgb) in quant.color_map_rgb().chunks(3).enumerate() {
+        if used[i] {
+            let _ = write!(
+                out,
+                "#{i};2;{};{};{}",
+                percent(rgb[0]),
+                percent(rgb[1]),
+                percent(rgb[2])
+            );
+        }
+    }
+
+    for top in (0..height).step_by(6) {
+        if top > 0 {
+            out.push('-');
+        }
+        // Colour -> the six-pixel column bits it covers in this band.
+        let mut band: BTreeMap<usize, Vec<u8>> = BTreeMap::new();
+        for y in top..(top + 6).min(height) {
+            for x in 0..width {
+                if let Some(colour) = index[(y * width + x) as usize] {
+                    band.entry(colour)
+                        .or_insert_with(|| vec![0; width as usize])[x as usize] |= 1 << (y - top);
+                }
+            }
+        }
+        for (n, (colour, bits)) in band.iter().enumerate() {
+            if n > 0 {
+                // Back to the band's start for the next colour.
+                out.push('$');
+            }
+            let _ = write!(out, "#{colour}");
+            let end = bits.iter().rposition(|&b| b != 0).map_or(0, |i| i + 1);
+            push_runs(&mut out, &bits[..end]);
+        }
+    }
+    out.push_str("\x1b\\");
+    out
+}
+
+/// Append the sixel characters for `bits`, writing repeats as `!<n><char>`.
+fn push_runs(out: &mut String, bits: &[u8]) {
+    let mut i = 0;
+    while i < bits.len() {
+        let run = bits[i..].iter().take_while(|&&b| b == bits[i]).count();
+        let c = char::from(0x3f + bits[i]);
+        if run > 3 {
+            let _ = write!(out, "!{run}{c}");
+        } else {
+            out.extend(std::iter::repeat_n(c, run));
+        }
+        i += run;
+    }
+}
+
+/// `img` as half-block text lines `cols` cells wide (two pixel rows per line,
+/// cells being about twice as tall as wide), to stand in for an ASCII logo.
+/// Colours are 24-bit with `truecolor`, else the nearest of the 256.
+pub fn block_art(img: &RgbaImage, cols: u32, truecolor: bool) -> Vec<String> {
+    let cols = cols.max(1);
+    let height =
+        (f64::from(cols) * f64::from(img.height()) / f64::from(img.width())).round() as u32;
+    let height = height.max(2).next_multiple_of(2);
+    half_blocks(
+        &image::imageops::resize(img, cols, height, FilterType::Triangle),
+        truecolor,
+    )
+}
+
+/// One line per two pixel rows: `▀` in the top pixel's colour over the bottom
+/// one's, `▄` when only the bottom pixel is opaque, a space when neither is.
+fn half_blocks(img: &RgbaImage, truecolor: bool) -> Vec<String> {
+    let colour = |ground: u8, p: &image::Rgba<u8>| match truecolor {
+        true => format!("{ground}8;2;{};{};{}", p[0], p[1], p[2]),
+        false => format!("{ground}8;5;{}", ansi256(p[0], p[1], p[2])),
+    };
+    let opaque = |p: &&image::Rgba<u8>| p[3] >= 128;
+    (0..img.height())
+        .step_by(2)
+        .map(|y| {
+            let mut line = String::new();
+            for x in 0..img.width() {
+                let top = Some(img.get_pixel(x, y)).filter(opaque);
+                let bottom = (y + 1 < img.height())
+                    .then(|| img.get_pixel(x, y + 1))
+                    .filter(opaque);
+                let _ = match (top, bottom) {
+                    (Some(t), Some(b)) => {
+                        write!(line, "\x1b[0;{};{}m▀", colour(3, t), colour(4, b))
+                    }
+                    (Some(t), None) => write!(line, "\x1b[0;{}m▀", colour(3, t)),
+                    (None, Some(b)) => write!(line, "\x1b[0;{}m▄", colour(3, b)),
+                    (None, None) => write!(line, "\x1b[0m "),
+                };
+            }
+            line.push_str("\x1b[0m");
+            line
+        })
+        .collect()
+}
+
+/// The nearest xterm 256-colour index: the grey ramp for greys, else the
+/// 6x6x6 cube.
+fn ansi256(r: u8, g: u8, b: u8) -> u8 {
+    if r.abs_diff(g) < 8 && g.abs_diff(b) < 8 {
+        let grey = (u16::from(r) + u16::from(g) + u16::from(b)) / 3;
+        return match grey {
+            0..8 => 16,
+            239.. => 231,
+            grey => 232 + ((grey - 8) / 10) as u8,
+        };
+    }
+    let level = |c: u8| ((u16::from(c) * 5 + 127) / 255) as u8;
+    16 + 36 * level(r) + 6 * level(g) + level(b)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn rejects_non_png() {
-        assert!(png_dimensions(b"not a png at all really").is_none());
+    fn decodes_jpeg_and_fits_to_the_cell_box() {
+        use image::{Rgb, RgbImage, codecs::jpeg::JpegEncoder};
+
+        let photo = RgbImage::from_pixel(400, 300, Rgb([200, 120, 40]));
+        let mut jpeg = Vec::new();
+        JpegEncoder::new(&mut jpeg).encode_image(&photo).unwrap();
+        let img = decode(&jpeg).expect("a JPEG decodes");
+        assert_eq!(img.dimensions(), (400, 300));
+        assert!(decode(b"not an image at all").is_none());
+
+        let (scaled, rows) = fit(&img, 4, (10, 20));
+        assert_eq!((scaled.dimensions(), rows), ((40, 30), 2));
+        let png = encode_png(&scaled).unwrap();
+        assert_eq!(decode(&png).unwrap().dimensions(), (40, 30));
+    }
+
+    #[test]
+    fn iterm2_inline_sequence() {
+        let mut out = Vec::new();
+        display_iterm2(&mut out, b"png", 40, 20).unwrap();
+        assert_eq!(
+            out,
+            b"\x1b]1337;File=inline=1;size=3;width=40;height=20;preserveAspectRatio=1:cG5n\x07"
+        );
     }
 
     #[test]
-    fn reads_ihdr_dimensions() {
-        // Minimal PNG signature + IHDR header for a 16x9 image.
-        let mut data = vec![0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
-        data.extend_from_slice(&[0, 0, 0, 13]); // IHDR length
-        data.extend_from_slice(b"IHDR");
-        data.extend_from_slice(&16u32.to_be_bytes());
-        data.extend_from_slice(&9u32.to_be_bytes());
-        assert_eq!(png_dimensions(&data), Some((16, 9)));
+    fn sixel_bands_and_runs() {
+        use image::Rgba;
+
+        // 5x7: a solid top band, then one row with a transparent last pixel.
+        let img = RgbaImage::from_fn(5, 7, |x, y| match (x, y) {
+            (4, 6) => Rgba([0, 0, 0, 0]),
+            _ => Rgba([255, 0, 0, 255]),
+        });
+        let sixel = encode_sixel(&img);
+        assert!(sixel.starts_with("\x1bP0;1;0q\"1;1;5;7#"));
+        assert!(sixel.ends_with("\x1b\\"));
+        let (_, bands) = sixel.split_once(";2;").unwrap();
+        let bands: Vec<&str> = bands.split('-').collect();
+        assert_eq!(bands.len(), 2);
+        assert!(bands[0].ends_with("!5~"));
+        assert!(bands[1].ends_with("!4@\x1b\\"));
+    }
+
+    #[test]
+    fn device_attributes_reply() {
+        assert_eq!(
+            parse_device_attributes(b"\x1b[?62;4;6;22c"),
+            Some(vec![62, 4, 6, 22])
+        );
+        assert_eq!(parse_device_attributes(b"x\x1b[?1;2c"), Some(vec![1, 2]));
+        assert_eq!(parse_device_attributes(b"\x1b[?62;4"), None);
+        assert_eq!(parse_device_attributes(b"\x1b[?62;xc"), None);
+    }
+
+    #[test]
+    fn half_block_cells() {
+        use image::Rgba;
+
+        let img = RgbaImage::from_fn(3, 2, |x, y| match (x, y) {
+            (0, 0) => Rgba([255, 0, 0, 255]),
+            (0, 1) => Rgba([0, 0, 255, 255]),
+            (1, 1) => Rgba([0, 255, 0, 255]),
+            _ => Rgba([0, 0, 0, 0]),
+        });
+        assert_eq!(
+            half_blocks(&img, true),
+            ["\x1b[0;38;2;255;0;0;48;2;0;0;255m▀\x1b[0;38;2;0;255;0m▄\x1b[0m \x1b[0m"]
+        );
+        assert_eq!(
+            half_blocks(&img, false),
+            ["\x1b[0;38;5;196;48;5;21m▀\x1b[0;38;5;46m▄\x1b[0m \x1b[0m"]
+        );
+        assert_eq!(ansi256(128, 128, 128), 244);
+        assert_eq!(block_art(&img, 6, true).len(), 2);
     }
 }
@@ -3,12 +3,31 @@ use std::io::Write;
 use serde_json::{Value, json};
 
 use crate::{
-    config::JsonRendererConfig,
-    probe::{ProbeList, ProbeResultValue, general_readout},
+    config::{BarDisplay, JsonRendererConfig, ProbeConfig},
+    probe::{ProbeError, ProbeList, ProbeOutput, ProbeResultValue, ProbeValue, general_readout},
 };
 
 use super::{RendererError, execute_probes_streaming};
 
+/// Version of the JSON document layout. Bumped whenever a field is renamed,
+/// removed or changes type; additions keep the version.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// `{ "kind": …, "message": … }` for a failed probe.
+fn error_details(err: &ProbeError) -> Value {
+    json!({ "kind": err.kind(), "message": err.to_string() })
+}
+
+/// Typed `data` for one probe value: the serialized [`ProbeValue`], with bare
+/// scalars/strings wrapped as `{ "value": … }` so `data` is always an object.
+fn typed_data(value: &ProbeValue) -> Value {
+    match serde_json::to_value(value) {
+        Ok(obj @ Value::Object(_)) => obj,
+        Ok(other) => json!({ "value": other }),
+        Err(_) => Value::Null,
+    }
+}
+
 /// Renders the probe results as a JSON document (neofetch `--json`).
 pub struct JsonRenderer {
     config: JsonRendererConfig,
@@ -20,7 +39,7 @@ impl JsonRenderer {
         let probe_list = config
             .probes
             .iter()
-            .map(|p| p.get_funcs())
+            .map(|p| p.get_funcs(config.probe_timeout))
             .collect::<Vec<_>>();
         Self { config, probe_list }
     }
@@ -32,19 +51,55 @@ impl JsonRenderer {
         let mut entries: Vec<Value> = vec![Value::Null; probes.len()];
         execute_probes_streaming(&self.probe_list, |index, label, result| {
             let id = probes[index].id();
-            entries[index] = match result {
-                Some(ProbeResultValue::Single(v)) => json!({
-                    "id": id,
-                    "label": label,
-                    "value": probes[index].format_value(&v),
-                }),
-                Some(ProbeResultValue::Multiple(vs)) => {
-                    let values: Vec<String> =
-                        vs.iter().map(|v| probes[index].format_value(v)).collect();
-                    json!({ "id": id, "label": label, "values": values })
+            let mut entry = json!({ "id": id, "label": label });
+            // Layout entries carry their own content; nothing was probed.
+            match &probes[index] {
+                ProbeConfig::Text { value, .. } => {
+                    entry["value"] = json!(value);
+                    entries[index] = entry;
+                    return;
+                }
+                ProbeConfig::Blank => {
+                    entries[index] = json!({ "id": id });
+                    return;
+                }
+                ProbeConfig::Header { text, underline } => {
+                    entries[index] = json!({ "id": id, "text": text, "underline": underline });
+                    return;
+                }
+                _ => {}
+            }
+            // Probes drawing a usage bar also report how full it is, 0 to 1.
+            let bar = probes[index].bar() != BarDisplay::Off;
+            let data = |v: &ProbeValue| {
+                let mut data = typed_data(v);
+                if let Some(ratio) = v.usage().filter(|_| bar) {
+                    data["ratio"] = json!(ratio);
                 }
-                None => json!({ "id": id, "label": label, "error": "unavailable" }),
+                data
             };
+            match result {
+                Ok(ProbeOutput { value, warning }) => {
+                    match value {
+                        ProbeResultValue::Multiple(vs) => {
+                            let values: Vec<String> =
+                                vs.iter().map(|v| probes[index].format_value(v)).collect();
+                            let data: Vec<Value> = vs.iter().map(data).collect();
+                            entry["values"] = json!(values);
+                            entry["data"] = json!(data);
+                        }
+                        ProbeResultValue::Single(v) => {
+                            entry["value"] = json!(probes[index].format_value(&v));
+                            entry["data"] = data(&v);
+                        }
+                    }
+                    if let Some(warning) = warning {
+                        entry["warning"] = json!(warning);
+                    }
+                }
+                Err(e) => entry["error"] = error_details(&e),
+            }
+            entries[index] = entry;
         });
 
         let distro = general_readout().distribution().ok();
@@ -54,6 +109,7 @@ impl JsonRenderer {
         };
 
         let out = json!({
+            "schema_version": SCHEMA_VERSION,
             "distro": distro,
             "host": host,
             "probes": entries,
@@ -65,3 +121,68 @@ impl JsonRenderer {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use serde_json::json;
+
+    use super::{error_details, typed_data};
+    use crate::probe::{BluetoothItem, ProbeError, ProbeValue};
+
+    #[test]
+    fn errors_report_kind_and_message() {
+        assert_eq!(
+            error_details(&ProbeError::TimedOut),
+            json!({ "kind": "timed_out", "message": "Timed out" })
+        );
+        assert_eq!(
+            error_details(&ProbeError::Other("no X server".into())),
+            json!({ "kind": "other", "message": "no X server" })
+        );
+    }
+
+    #[test]
+    fn data_carries_named_typed_fields() {
+        assert_eq!(
+            typed_data(&ProbeValue::Memory(1024, 2048)),
+            json!({ "used_kib": 1024, "total_kib": 2048 })
+        );
+        assert_eq!(
+            typed_data(&ProbeValue::Disk(
+                PathBuf::from("/"),
+                "nvme0n1p2".into(),
+                10,
+                20
+            )),
+            json!({ "mount": "/", "device": "nvme0n1p2", "used_bytes": 10, "total_bytes": 20 })
+        );
+        assert_eq!(
+            typed_data(&ProbeValue::Uptime(3600)),
+            json!({ "seconds": 3600 })
+        );
+        assert_eq!(
+            typed_data(&ProbeValue::Packages(vec![
+                ("dpkg".into(), 1234),
+                ("flatpak".into(), 5)
+            ])),
+            json!({ "dpkg": 1234, "flatpak": 5 })
+        );
+        assert_eq!(
+            typed_data(&ProbeValue::CPU("AMD Ryzen 7 7800X3D".into(), Some(49.2))),
+            json!({ "model": "AMD Ryzen 7 7800X3D", "temp_celsius": 49.2 })
+        );
+        assert_eq!(
+            typed_data(&ProbeValue::Kernel("6.8.4".into())),
+            json!({ "value": "6.8.4" })
+        );
+        assert_eq!(
+            typed_data(&ProbeValue::Bluetooth(BluetoothItem::Device {
+                name: "WH-1000XM4".into(),
+                battery: Some(80),
+            })),
+            json!({ "kind": "device", "name": "WH-1000XM4", "battery": 80 })
+        );
+    }
+}
@@ -1,33 +1,98 @@
 use libmacchina::traits::ReadoutError;
+use std::sync::{
+    Arc,
+    mpsc::{self, RecvTimeoutError},
+};
+use std::time::Instant;
 use thiserror::Error;
-use tracing::info_span;
 
-use crate::probe::{ProbeList, ProbeResultValue};
+use tracing::{info_span, warn};
+
+use crate::probe::{ProbeError, ProbeList, ProbeResult, with_deadline};
 
 pub mod image;
 pub mod json;
 pub mod neofetch;
 
+/// Run every probe on its own thread and hand each result to `on_result` as
+/// it arrives. A probe still running at its deadline is reported as
+/// `ProbeError::TimedOut` and abandoned: its thread is detached (subprocesses
+/// spawned through `run_command` are killed by the probe itself) so the
+/// renderer never waits on it.
 pub fn execute_probes_streaming<F>(probe_list: &ProbeList, mut on_result: F)
 where
-    F: FnMut(usize, &str, Option<ProbeResultValue>),
+    F: FnMut(usize, &str, ProbeResult),
 {
-    let (tx, rx) = std::sync::mpsc::channel();
-    std::thread::scope(|s| {
-        for (index, (label, probe_fn)) in probe_list.iter().enumerate() {
-            let tx = tx.clone();
-            let label = label.clone();
-            s.spawn(move || {
-                let _span = info_span!("probe", name = %label).entered();
-                let result = probe_fn().ok();
-                let _ = tx.send((index, label, result));
-            });
+    let start = Instant::now();
+    let (tx, rx) = mpsc::channel();
+    let mut deadlines: Vec<Option<Instant>> = Vec::with_capacity(probe_list.len());
+    for (index, (label, probe_fn, timeout)) in probe_list.iter().enumerate() {
+        let deadline = timeout.map(|t| start + t);
+        deadlines.push(deadline);
+        let tx = tx.clone();
+        let label = label.clone();
+        let probe_fn = Arc::clone(probe_fn);
+        std::thread::spawn(move || {
+            let _span = info_span!("probe", name = %label).entered();
+            let result = with_deadline(deadline, || probe_fn());
+            let _ = tx.send((index, result));
+        });
+    }
+    drop(tx);
+
+    let mut pending = probe_list.len();
+    let mut done = vec![false; probe_list.len()];
+    let mut finish = |index: usize, result: ProbeResult, done: &mut [bool]| {
+        done[index] = true;
+        on_result(index, &probe_list[index].0, result);
+    };
+    while pending > 0 {
+        let next_deadline = (0..done.len())
+            .filter(|&i| !done[i])
+            .filter_map(|i| deadlines[i])
+            .min();
+        let received = match next_deadline {
+            Some(deadline) => {
+                match rx.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                    Ok(msg) => Some(msg),
+                    Err(RecvTimeoutError::Timeout) => None,
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+            }
+            None => match rx.recv() {
+                Ok(msg) => Some(msg),
+                Err(_) => break,
+            },
+        };
+        match received {
+            // A probe already reported as timed out finished after all.
+            Some((index, _)) if done[index] => continue,
+            Some((index, result)) => {
+                finish(index, result, &mut done);
+                pending -= 1;
+            }
+            None => {
+                let now = Instant::now();
+                for index in 0..done.len() {
+                    if !done[index] && deadlines[index].is_some_and(|d| d <= now) {
+                        warn!(probe = %probe_list[index].0, "probe timed out");
+                        finish(index, Err(ProbeError::TimedOut), &mut done);
+                        pending -= 1;
+                    }
+                }
+            }
         }
-        drop(tx);
-        for (index, label, result) in rx {
-            on_result(index, &label, result);
+    }
+    // Senders only vanish early when a probe thread panicked.
+    for index in 0..done.len() {
+        if !done[index] {
+            finish(
+                index,
+                Err(ProbeError::Other("probe panicked".to_string())),
+                &mut done,
+            );
         }
-    });
+    }
 }
 
 #[derive(Error, Debug)]
@@ -43,3 +108,78 @@ impl From<ReadoutError> for RendererError {
         RendererError::ReadoutError(err)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    use super::execute_probes_streaming;
+    use crate::probe::{ProbeError, ProbeList, ProbeResultValue, ProbeValue};
+
+    #[test]
+    fn hung_probe_times_out_without_blocking_the_rest() {
+        let probe_list: ProbeList = vec![
+            (
+                "Fast".to_string(),
+                Arc::new(|| Ok(ProbeResultValue::Single(ProbeValue::Locale("C".into())).into())),
+                Some(Duration::from_millis(500)),
+            ),
+            (
+                "Hung".to_string(),
+                Arc::new(|| {
+                    std::thread::sleep(Duration::from_secs(30));
+                    Err(ProbeError::MetricsUnavailable)
+                }),
+                Some(Duration::from_millis(50)),
+            ),
+        ];
+        let start = Instant::now();
+        let mut seen = Vec::new();
+        execute_probes_streaming(&probe_list, |index, label, result| {
+            seen.push((index, label.to_string(), result.map_err(|e| e.to_string())));
+        });
+        assert!(start.elapsed() < Duration::from_secs(5));
+        seen.sort_by_key(|(i, ..)| *i);
+        assert!(seen[0].2.is_ok());
+        assert_eq!(seen[1].1, "Hung");
+        assert_eq!(
+            seen[1].2.as_ref().err().map(String::as_str),
+            Some("Timed out")
+        );
+    }
+
+    #[test]
+    fn late_result_of_a_timed_out_probe_is_dropped() {
+        let probe_list: ProbeList = vec![
+            (
+                "Late".to_string(),
+                Arc::new(|| {
+                    std::thread::sleep(Duration::from_millis(100));
+                    Ok(ProbeResultValue::Single(ProbeValue::Locale("late".into())).into())
+                }),
+                Some(Duration::from_millis(20)),
+            ),
+            (
+                "Slow".to_string(),
+                Arc::new(|| {
+                    std::thread::sleep(Duration::from_millis(400));
+                    Ok(ProbeResultValue::Single(ProbeValue::Locale("C".into())).into())
+                }),
+                None,
+            ),
+        ];
+        let mut seen = Vec::new();
+        execute_probes_streaming(&probe_list, |index, _, result| {
+            seen.push((index, result.map_err(|e| e.to_string())));
+        });
+        seen.sort_by_key(|(i, _)| *i);
+        let indices: Vec<_> = seen.iter().map(|(i, _)| *i).collect();
+        assert_eq!(indices, [0, 1]);
+        assert_eq!(
+            seen[0].1.as_ref().err().map(String::as_str),
+            Some("Timed out")
+        );
+        assert!(seen[1].1.is_ok());
+    }
+}
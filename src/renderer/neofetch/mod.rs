@@ -11,8 +11,8 @@ use tracing::debug;
 
 use crate::{
     ascii::{get_ascii_art, get_distro_color, get_filler},
-    config::{Backend, NeofetchRendererConfig},
-    probe::{ProbeList, ProbeResultValue, general_readout},
+    config::{Backend, BarDisplay, BarOptions, NeofetchRendererConfig, ProbeConfig},
+    probe::{ProbeError, ProbeList, ProbeOutput, ProbeResult, general_readout},
 };
 
 use super::{RendererError, execute_probes_streaming};
@@ -77,12 +77,88 @@ fn resolve_colors(colors: &[u8], palette: &[u8; 6], primary: Color) -> ResolvedC
     }
 }
 
+/// Usage bar colours `(elapsed, total)`. Unset colours follow neofetch's
+/// `bar_color_*="distro"`: the used part in the terminal's foreground, the rest
+/// in the logo's first colour when that is 3, 6 or 7, else its second.
+fn bar_colors(bars: &BarOptions, palette: &[u8; 6]) -> (Color, Color) {
+    // neofetch's `color 7` is the terminal foreground.
+    let color = |c: u8| match c {
+        7 => Color::Reset,
+        c => Color::AnsiValue(c),
+    };
+    let total = match palette[0] {
+        3 | 6 | 7 => palette[0],
+        _ => palette[1],
+    };
+    (
+        bars.color_elapsed.map_or(Color::Reset, color),
+        color(bars.color_total.unwrap_or(total)),
+    )
+}
+
+/// One value line of a probe: its text, plus how full the metric is when the
+/// probe draws a usage bar.
+#[derive(Clone, Debug)]
+struct Line {
+    text: String,
+    usage: Option<f64>,
+}
+
+impl Line {
+    fn text(text: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            usage: None,
+        }
+    }
+
+    /// The line as plain text, with its bar drawn in `bars`' characters.
+    fn plain(&self, display: BarDisplay, bars: &BarOptions) -> String {
+        match (display, self.usage) {
+            (BarDisplay::Bar, Some(usage)) => bars.render(usage),
+            (BarDisplay::Infobar, Some(usage)) => format!("{} {}", self.text, bars.render(usage)),
+            (BarDisplay::Barinfo, Some(usage)) => format!("{} {}", bars.render(usage), self.text),
+            _ => self.text.clone(),
+        }
+    }
+}
+
+/// Display lines for one probe result; empty when the probe failed or timed
+/// out. Errors and warnings aren't part of neofetch's text output, so they only
+/// reach the log.
+fn format_result(probe: &ProbeConfig, result: ProbeResult) -> Vec<Line> {
+    match result {
+        Ok(ProbeOutput { value, warning }) => {
+            if let Some(warning) = warning {
+                debug!("Warning while probing {}: {}", probe.label(), warning);
+            }
+            let bar = probe.bar() != BarDisplay::Off;
+            value
+                .into_values()
+                .iter()
+                .map(|v| Line {
+                    text: probe.format_value(v),
+                    usage: v.usage().filter(|_| bar),
+                })
+                .collect()
+        }
+        Err(ProbeError::TimedOut) => {
+            debug!("Probe {} timed out, leaving it out", probe.label());
+            vec![]
+        }
+        Err(e) => {
+            debug!("Error while probing {}: {}", probe.label(), e);
+            vec![]
+        }
+    }
+}
+
 impl NeofetchRenderer {
     pub fn new(config: NeofetchRendererConfig) -> Self {
         let probe_list = config
             .probes
             .iter()
-            .map(|p| p.get_funcs())
+            .map(|p| p.get_funcs(config.probe_timeout))
             .collect::<Vec<_>>();
         Self { config, probe_list }
     }
@@ -110,8 +186,8 @@ impl NeofetchRenderer {
     pub fn draw(&self) -> Result<(), RendererError> {
         use libmacchina::traits::GeneralReadout as _;
 
-        // The Kitty image backend short-circuits the ASCII renderer when it
-        // applies; otherwise it returns false and we fall through to ASCII.
+        // An image backend short-circuits the ASCII renderer when it applies;
+        // otherwise it returns false and we fall through to ASCII.
         if self.draw_image()? {
             return Ok(());
         }
@@ -125,12 +201,16 @@ impl NeofetchRenderer {
         let distro = self.config.ascii.distro.clone().unwrap_or(detected);
         debug!("Logo distro: {}", distro);
 
+        let no_color = std::env::var_os("NO_COLOR").is_some();
         let (full_art, full_width, base_palette) = get_ascii_art(&distro);
+        // `backend = blocks` puts the image, as half-block text, in the logo's place.
+        let blocks = self.block_art(no_color);
+        let block_lines: Vec<&str> = blocks.iter().flatten().map(String::as_str).collect();
         // `backend = off` (neofetch --off) drops the logo entirely.
-        let (ascii_art, ascii_width): (&[&str], usize) = if self.config.backend == Backend::Off {
-            (&[], 0)
-        } else {
-            (full_art, full_width)
+        let (ascii_art, ascii_width): (&[&str], usize) = match self.config.backend {
+            Backend::Off => (&[], 0),
+            _ if blocks.is_some() => (&block_lines, self.config.image_cols.max(1).into()),
+            _ => (full_art, full_width),
         };
         let primary_color = get_distro_color(&distro);
         // `ascii_colors` overrides the logo palette (padded with the logo's own).
@@ -142,7 +222,6 @@ impl NeofetchRenderer {
             p
         };
         let ascii_bold = self.config.ascii.bold;
-        let no_color = std::env::var_os("NO_COLOR").is_some();
         let filler = get_filler(ascii_width);
         // Expand `${cN}` markers at render time (or strip them under NO_COLOR).
         let get_art = |idx: usize| -> String {
@@ -155,6 +234,8 @@ impl NeofetchRenderer {
         };
 
         let colors = resolve_colors(&self.config.colors, &palette, primary_color);
+        let bars = &self.config.bars;
+        let (bar_elapsed, bar_total) = bar_colors(bars, &palette);
         let bold = self.config.bold;
         let sep = self.config.separator.as_str();
         let sep_width = sep.chars().count();
@@ -202,81 +283,171 @@ impl NeofetchRenderer {
         // 0-based column where a value starts: ascii + "   " + label + sep + " ".
         // Labels are not padded (neofetch puts the colon right after the label),
         // so the column depends on each probe's own label length.
-        let value_col = |title_len: usize| (ascii_width + 3 + title_len + sep_width + 1) as u16;
+        // An unlabelled probe has no separator either; its value starts right away.
+        let value_col = |title_len: usize| match title_len {
+            0 => (ascii_width + 3) as u16,
+            _ => (ascii_width + 3 + title_len + sep_width + 1) as u16,
+        };
         // Per-probe config, aligned with `probe_list` by index, for option-aware formatting.
         let probes = &self.config.probes;
 
+        // Write a value: its text, with the probe's usage bar instead of,
+        // after or before it (neofetch `bar`/`infobar`/`barinfo`).
+        let put_value = |w: &mut std::io::BufWriter<_>, display: BarDisplay, line: &Line| {
+            let put_bar = |w: &mut std::io::BufWriter<_>, usage: f64| {
+                let (elapsed, total) = bars.segments(usage);
+                if bars.border {
+                    Self::put(w, Color::Reset, false, "[")?;
+                }
+                Self::put(w, bar_elapsed, false, &elapsed)?;
+                Self::put(w, bar_total, false, &total)?;
+                if bars.border {
+                    Self::put(w, Color::Reset, false, "]")?;
+                }
+                Ok::<_, std::io::Error>(())
+            };
+            match (display, line.usage) {
+                (BarDisplay::Bar, Some(usage)) => put_bar(w, usage),
+                (BarDisplay::Infobar, Some(usage)) => {
+                    Self::put(w, colors.info, false, &line.text)?;
+                    queue!(w, Print(" "))?;
+                    put_bar(w, usage)
+                }
+                (BarDisplay::Barinfo, Some(usage)) => {
+                    put_bar(w, usage)?;
+                    queue!(w, Print(" "))?;
+                    Self::put(w, colors.info, false, &line.text)
+                }
+                _ => Self::put(w, colors.info, false, &line.text),
+            }
+        };
         // Emit one full probe line: art, label, separator, value.
-        let put_line = |w: &mut std::io::BufWriter<_>, art: &str, label: &str, value: &str| {
-            Self::put(w, primary_color, false, art)?;
-            queue!(w, Print("   "))?;
-            Self::put(w, colors.subtitle, bold, label)?;
-            Self::put(w, colors.colon, bold, sep)?;
-            queue!(w, Print(" "))?;
-            Self::put(w, colors.info, false, value)?;
-            queue!(w, Print("\n"))
+        let put_line =
+            |w: &mut std::io::BufWriter<_>, art: &str, probe: &ProbeConfig, line: &Line| {
+                Self::put(w, primary_color, false, art)?;
+                queue!(w, Print("   "))?;
+                Self::put(w, colors.subtitle, bold, probe.label())?;
+                Self::put(w, colors.colon, bold, sep)?;
+                queue!(w, Print(" "))?;
+                put_value(w, probe.bar(), line)?;
+                queue!(w, Print("\n"))
+            };
+        // Emit one entry — a probe's value lines, or a layout entry drawn in
+        // place — advancing `art_idx` by the rows it used.
+        let put_entry = |w: &mut std::io::BufWriter<_>,
+                         art_idx: &mut usize,
+                         probe: &ProbeConfig,
+                         lines: &[Line]| {
+            let mut row = |w: &mut std::io::BufWriter<_>, color: Color, bold: bool, text: &str| {
+                Self::put(w, primary_color, false, &get_art(*art_idx))?;
+                if !text.is_empty() {
+                    queue!(w, Print("   "))?;
+                    Self::put(w, color, bold, text)?;
+                }
+                *art_idx += 1;
+                queue!(w, Print("\n"))
+            };
+            match probe {
+                ProbeConfig::Blank => row(w, colors.info, false, ""),
+                ProbeConfig::Header { text, underline } => {
+                    row(w, colors.title, bold, text)?;
+                    if *underline {
+                        let rule = self.config.underline_char.repeat(text.chars().count());
+                        row(w, colors.underline, false, &rule)?;
+                    }
+                    Ok(())
+                }
+                ProbeConfig::Text { label, value } if label.is_empty() => {
+                    row(w, colors.info, false, value)
+                }
+                // Without a label only the value is shown (neofetch `info field`).
+                _ if probe.label().is_empty() => {
+                    for line in lines {
+                        Self::put(w, primary_color, false, &get_art(*art_idx))?;
+                        queue!(w, Print("   "))?;
+                        put_value(w, probe.bar(), line)?;
+                        queue!(w, Print("\n"))?;
+                        *art_idx += 1;
+                    }
+                    Ok(())
+                }
+                _ => {
+                    for line in lines {
+                        // Repeat the label on every line (e.g. one "GPU:" per GPU),
+                        // matching neofetch rather than leaving orphaned values.
+                        put_line(w, &get_art(*art_idx), probe, line)?;
+                        *art_idx += 1;
+                    }
+                    Ok(())
+                }
+            }
         };
 
         if !is_tty {
             // Non-TTY: run probes in parallel, print results sequentially
-            let mut all_results: Vec<Option<Vec<String>>> = vec![None; n_probes];
+            let mut all_results: Vec<Option<Vec<Line>>> = vec![None; n_probes];
             execute_probes_streaming(&self.probe_list, |index, _, result| {
-                all_results[index] = Some(match result {
-                    Some(ProbeResultValue::Single(v)) => vec![probes[index].format_value(&v)],
-                    Some(ProbeResultValue::Multiple(vs)) => {
-                        vs.iter().map(|v| probes[index].format_value(v)).collect()
-                    }
-                    None => vec![],
-                });
+                all_results[index] = Some(format_result(&probes[index], result));
             });
 
-            for (i, (title, _)) in self.probe_list.iter().enumerate() {
-                let strings = match all_results[i].as_deref() {
+            for (i, (title, ..)) in self.probe_list.iter().enumerate() {
+                let lines = match all_results[i].as_deref() {
                     Some([]) | None => {
                         debug!("Error while probing {}", title);
                         continue;
                     }
-                    Some(ss) => ss.to_vec(),
+                    Some(ls) => ls.to_vec(),
                 };
-                for s in strings.iter() {
-                    // Repeat the label on every line (e.g. one "GPU:" per GPU),
-                    // matching neofetch rather than leaving orphaned values.
-                    put_line(&mut w, &get_art(art_idx), title, s)?;
-                    art_idx += 1;
-                }
+                put_entry(&mut w, &mut art_idx, &probes[i], &lines)?;
             }
         } else {
             // TTY: progressive rendering with cursor movement
 
-            // Phase 1: print all placeholder lines immediately (label + separator).
-            for (i, (title, _)) in self.probe_list.iter().enumerate() {
-                Self::put(&mut w, primary_color, false, &get_art(probe_art_start + i))?;
+            // Phase 1: print all placeholder lines immediately (label + separator);
+            // layout entries are already final, so draw them outright. A
+            // `row_start` per entry tracks where each value goes (an underlined
+            // header takes two rows).
+            let mut row_art = probe_art_start;
+            let mut row_start = Vec::with_capacity(n_probes);
+            for (i, (title, ..)) in self.probe_list.iter().enumerate() {
+                row_start.push(row_art - probe_art_start);
+                let probe = &probes[i];
+                if probe.is_layout() {
+                    let text = match probe {
+                        ProbeConfig::Text { value, .. } => vec![Line::text(value)],
+                        _ => vec![],
+                    };
+                    put_entry(&mut w, &mut row_art, probe, &text)?;
+                    continue;
+                }
+                Self::put(&mut w, primary_color, false, &get_art(row_art))?;
                 queue!(w, Print("   "))?;
-                Self::put(&mut w, colors.subtitle, bold, title)?;
-                Self::put(&mut w, colors.colon, bold, sep)?;
-                queue!(w, Print(" \n"))?;
+                if !title.is_empty() {
+                    Self::put(&mut w, colors.subtitle, bold, title)?;
+                    Self::put(&mut w, colors.colon, bold, sep)?;
+                    queue!(w, Print(" "))?;
+                }
+                queue!(w, Print("\n"))?;
+                row_art += 1;
             }
+            let n_rows = row_art - probe_art_start;
             w.flush()?;
             // Save cursor position at the bottom of the probe section
             execute!(w, cursor::SavePosition)?;
 
             // Phase 2: fill in values as each probe completes
-            let mut results: Vec<Option<Vec<String>>> = vec![None; n_probes];
+            let mut results: Vec<Option<Vec<Line>>> = vec![None; n_probes];
             let mut needs_rerender = false;
 
             execute_probes_streaming(&self.probe_list, |index, _label, result| {
-                let strings: Vec<String> = match result {
-                    Some(ProbeResultValue::Single(v)) => vec![probes[index].format_value(&v)],
-                    Some(ProbeResultValue::Multiple(vs)) => {
-                        vs.iter().map(|v| probes[index].format_value(v)).collect()
-                    }
-                    None => vec![],
-                };
+                let lines = format_result(&probes[index], result);
 
-                if strings.len() == 1 {
+                if probes[index].is_layout() {
+                    // Drawn in phase 1.
+                } else if lines.len() == 1 {
                     // Single value: move cursor to the right line and fill in,
                     // at the column just after this probe's own label.
-                    let lines_up = (n_probes - index) as u16;
+                    let lines_up = (n_rows - row_start[index]) as u16;
                     let col = value_col(self.probe_list[index].0.chars().count());
                     let _ = execute!(
                         w,
@@ -284,14 +455,14 @@ impl NeofetchRenderer {
                         cursor::MoveUp(lines_up),
                         cursor::MoveToColumn(col),
                     );
-                    let _ = Self::put(&mut w, colors.info, false, &strings[0]);
+                    let _ = put_value(&mut w, probes[index].bar(), &lines[0]);
                     let _ = execute!(w, cursor::RestorePosition);
                 } else {
                     // Zero (failure) or multiple values: needs a re-render pass
                     needs_rerender = true;
                 }
 
-                results[index] = Some(strings);
+                results[index] = Some(lines);
             });
 
             // Phase 3: if any probe had 0 or multiple lines, re-render the probe section
@@ -299,30 +470,27 @@ impl NeofetchRenderer {
                 execute!(
                     w,
                     cursor::RestorePosition,
-                    cursor::MoveUp(n_probes as u16),
+                    cursor::MoveUp(n_rows as u16),
                     cursor::MoveToColumn(0),
                     terminal::Clear(terminal::ClearType::FromCursorDown),
                 )?;
                 let mut ra_idx = probe_art_start;
-                for (i, (title, _)) in self.probe_list.iter().enumerate() {
-                    let strings = match results[i].as_deref() {
+                for (i, (title, ..)) in self.probe_list.iter().enumerate() {
+                    let lines = match results[i].as_deref() {
                         Some([]) | None => {
                             debug!("Error while probing {}", title);
                             continue;
                         }
-                        Some(ss) => ss.to_vec(),
+                        Some(ls) => ls.to_vec(),
                     };
-                    for s in strings.iter() {
-                        put_line(&mut w, &get_art(ra_idx), title, s)?;
-                        ra_idx += 1;
-                    }
+                    put_entry(&mut w, &mut ra_idx, &probes[i], &lines)?;
                 }
                 art_idx = ra_idx;
                 w.flush()?;
             } else {
                 // Ensure cursor is at the bottom of the probe section
                 execute!(w, cursor::RestorePosition)?;
-                art_idx = probe_art_start + n_probes;
+                art_idx = probe_art_start + n_rows;
             }
         }
 
@@ -380,34 +548,80 @@ impl NeofetchRenderer {
         Ok(())
     }
 
-    /// If the Kitty image backend applies, render the image with the info block
-    /// beside it and return `true`; otherwise return `false` to fall back to
-    /// ASCII. Falls back whenever the terminal isn't Kitty or the source isn't
-    /// a readable PNG, so non-Kitty terminals are unaffected.
+    /// If an image backend applies, render the image with the info block beside
+    /// it and return `true`; otherwise return `false` to fall back to ASCII.
+    /// Falls back whenever the terminal can't show the backend's images or the
+    /// source isn't a readable image, so other terminals are unaffected.
     fn draw_image(&self) -> Result<bool, RendererError> {
         use crate::renderer::image;
 
-        if self.config.backend != Backend::Kitty {
-            return Ok(false);
-        }
+        // Checked first: asking the terminal about sixel costs a round trip.
         let Some(src) = self.config.image_source.clone() else {
             return Ok(false);
         };
-        if !image::kitty_supported() {
+        let supported = match self.config.backend {
+            Backend::Kitty => image::kitty_supported(),
+            Backend::Iterm2 => image::iterm2_supported(),
+            Backend::Sixel => image::sixel_supported(),
+            Backend::Ascii | Backend::Blocks | Backend::Off => return Ok(false),
+        };
+        if !supported {
             return Ok(false);
         }
-        let Ok(png) = std::fs::read(&src) else {
+        let Some(img) = std::fs::read(&src)
+            .ok()
+            .and_then(|data| image::decode(&data))
+        else {
             return Ok(false);
         };
-        let Some((iw, ih)) = image::png_dimensions(&png) else {
-            return Ok(false);
-        };
-
         let cols = self.config.image_cols.max(1) as u32;
-        // Terminal cells are ~2x taller than wide, so halve the row estimate.
-        let rows = ((cols as f64 * ih as f64 / iw as f64) / 2.0)
-            .round()
-            .max(1.0) as u32;
+        // Scale to the cell box first: only what is shown gets sent.
+        let (img, rows) = image::fit(&img, cols, image::cell_size());
+
+        match self.config.backend {
+            Backend::Sixel => self.draw_beside(cols, rows, |w| image::display_sixel(w, &img)),
+            backend => {
+                let Some(png) = image::encode_png(&img) else {
+                    return Ok(false);
+                };
+                self.draw_beside(cols, rows, |w| match backend {
+                    Backend::Iterm2 => image::display_iterm2(w, &png, cols, rows),
+                    _ => image::display_png(w, &png, cols, rows),
+                })
+            }
+        }
+    }
+
+    /// The `blocks` backend's logo: `image_source` as half-block lines, or
+    /// `None` to use ASCII art (another backend, no colour, or a source that
+    /// isn't a readable image).
+    fn block_art(&self, no_color: bool) -> Option<Vec<String>> {
+        use crate::renderer::image;
+
+        if self.config.backend != Backend::Blocks || no_color {
+            return None;
+        }
+        let data = std::fs::read(self.config.image_source.as_ref()?).ok()?;
+        let img = image::decode(&data)?;
+        let truecolor = matches!(
+            std::env::var("COLORTERM").as_deref(),
+            Ok("truecolor" | "24bit")
+        );
+        Some(image::block_art(
+            &img,
+            self.config.image_cols.into(),
+            truecolor,
+        ))
+    }
+
+    /// Lay out an image `cols`x`rows` cells in size with the info block to its
+    /// right: `draw` writes the image at the top-left cursor position.
+    fn draw_beside(
+        &self,
+        cols: u32,
+        rows: u32,
+        draw: impl FnOnce(&mut std::io::BufWriter<std::io::StdoutLock<'static>>) -> std::io::Result<()>,
+    ) -> Result<bool, RendererError> {
         let pad = " ".repeat(cols as usize + 2);
 
         let lines = self.build_info_lines()?;
@@ -420,7 +634,7 @@ impl NeofetchRenderer {
             write!(w, "{pad}{line}\r\n")?;
         }
         execute!(w, cursor::RestorePosition)?;
-        image::display_png(&mut w, &png, cols, rows)?;
+        draw(&mut w)?;
         let total = lines.len().max(rows as usize) as u16;
         execute!(w, cursor::RestorePosition, cursor::MoveToNextLine(total))?;
         w.flush()?;
@@ -434,15 +648,9 @@ impl NeofetchRenderer {
 
         let probes = &self.config.probes;
 
-        let mut results: Vec<Option<Vec<String>>> = vec![None; self.probe_list.len()];
+        let mut results: Vec<Option<Vec<Line>>> = vec![None; self.probe_list.len()];
         execute_probes_streaming(&self.probe_list, |index, _, result| {
-            results[index] = Some(match result {
-                Some(ProbeResultValue::Single(v)) => vec![probes[index].format_value(&v)],
-                Some(ProbeResultValue::Multiple(vs)) => {
-                    vs.iter().map(|v| probes[index].format_value(v)).collect()
-                }
-                None => vec![],
-            });
+            results[index] = Some(format_result(&probes[index], result));
         });
 
         let sep = &self.config.separator;
@@ -463,9 +671,26 @@ impl NeofetchRenderer {
             }
         }
         for (i, p) in probes.iter().enumerate() {
-            if let Some(strings) = &results[i] {
-                for s in strings {
-                    lines.push(format!("{}{sep} {s}", p.label()));
+            match p {
+                ProbeConfig::Blank => lines.push(String::new()),
+                ProbeConfig::Header { text, underline } => {
+                    lines.push(text.clone());
+                    if *underline {
+                        lines.push(self.config.underline_char.repeat(text.chars().count()));
+                    }
+                }
+                ProbeConfig::Text { label, value } if label.is_empty() => lines.push(value.clone()),
+                _ if p.label().is_empty() => lines.extend(
+                    results[i]
+                        .iter()
+                        .flatten()
+                        .map(|l| l.plain(p.bar(), &self.config.bars)),
+                ),
+                _ => {
+                    for l in results[i].iter().flatten() {
+                        let value = l.plain(p.bar(), &self.config.bars);
+                        lines.push(format!("{}{sep} {value}", p.label()));
+                    }
                 }
             }
         }
@@ -475,7 +700,11 @@ impl NeofetchRenderer {
 
 #[cfg(test)]
 mod tests {
-    use super::resolve_colors;
+    use super::{Line, bar_colors, format_result, resolve_colors};
+    use crate::{
+        config::{BarDisplay, BarOptions, ProbeConfig},
+        probe::{ProbeResultValue, ProbeValue},
+    };
     use crossterm::style::Color;
 
     #[test]
@@ -510,4 +739,54 @@ mod tests {
         assert_eq!(c.colon, Color::AnsiValue(5));
         assert_eq!(c.info, Color::AnsiValue(6));
     }
+
+    #[test]
+    fn usage_bars() {
+        let probe: ProbeConfig =
+            toml::from_str("Memory = { label = \"Memory\", bar = \"infobar\" }").unwrap();
+        let lines = format_result(
+            &probe,
+            Ok(ProbeResultValue::Single(ProbeValue::Memory(1, 4)).into()),
+        );
+        assert_eq!(lines[0].usage, Some(0.25));
+
+        let bars = BarOptions {
+            length: 8,
+            ..BarOptions::default()
+        };
+        let line = Line {
+            text: "1MiB / 4MiB".to_string(),
+            usage: lines[0].usage,
+        };
+        assert_eq!(line.plain(BarDisplay::Bar, &bars), "[--======]");
+        assert_eq!(
+            line.plain(BarDisplay::Infobar, &bars),
+            "1MiB / 4MiB [--======]"
+        );
+        assert_eq!(
+            line.plain(BarDisplay::Barinfo, &bars),
+            "[--======] 1MiB / 4MiB"
+        );
+        assert_eq!(line.plain(BarDisplay::Off, &bars), "1MiB / 4MiB");
+        assert_eq!(bars.render(1.5), "[--------]");
+
+        // Unset colours: foreground used part, the rest in c1 when it is 3/6/7, else c2.
+        assert_eq!(
+            bar_colors(&bars, &[6, 4, 1, 1, 1, 1]),
+            (Color::Reset, Color::AnsiValue(6))
+        );
+        assert_eq!(
+            bar_colors(&bars, &[1, 4, 1, 1, 1, 1]),
+            (Color::Reset, Color::AnsiValue(4))
+        );
+        let bars = BarOptions {
+            color_elapsed: Some(2),
+            color_total: Some(7),
+            ..bars
+        };
+        assert_eq!(
+            bar_colors(&bars, &[1; 6]),
+            (Color::AnsiValue(2), Color::Reset)
+        );
+    }
 }